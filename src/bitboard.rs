@@ -0,0 +1,539 @@
+use crate::WINNING_LINES;
+use crate::types::{Cell, Player};
+
+/// Mask with one bit set for every cell on the board.
+pub(crate) const FULL_MASK: u16 = 0b1_1111_1111;
+
+/// Masks of the center, the corners and the edges, in the order the search
+/// tries them: the more lines a cell lies on, the more promising it is.
+const CELLS_BY_LINES: [u16; 3] = [0b000_010_000, 0b101_000_101, 0b010_101_010];
+
+/// The most winning lines any supported win length produces (36, for a win
+/// length of 2 with wrap-around lines).
+const MAX_LINES: usize = 36;
+
+/// The winning lines of the board for a given win length, as bitmasks with
+/// one bit per cell index.
+///
+/// A line is any `win_len` consecutive cells along a row, column or diagonal.
+/// For the standard win length of 3 these are the 8 familiar rows, columns and
+/// diagonals of [`WINNING_LINES`], in the same order.
+///
+/// With `wrap`, lines continue across the edges of the board onto the
+/// opposite side, as on a torus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct WinLines {
+    masks: [u16; MAX_LINES],
+    /// The cell indices of each line, in order along the line; only the first `win_len` are used.
+    cells: [[usize; 3]; MAX_LINES],
+    len: usize,
+    win_len: usize,
+    wrap: bool,
+}
+
+impl WinLines {
+    /// Generates the winning lines for `win_len` marks in a row, which must be
+    /// in `1..=3`, wrapping around the edges of the board if `wrap` is set.
+    pub(crate) const fn new(win_len: usize, wrap: bool) -> Self {
+        if win_len == 3 && !wrap {
+            return STANDARD_LINES;
+        }
+
+        // Directions as (row, column) steps: rows, columns, diagonals, anti-diagonals.
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+        let mut lines = Self {
+            masks: [0; MAX_LINES],
+            cells: [[0; 3]; MAX_LINES],
+            len: 0,
+            win_len,
+            wrap,
+        };
+        let mut d = 0;
+        while d < DIRECTIONS.len() {
+            let (dr, dc) = DIRECTIONS[d];
+            let mut start = 0;
+            while start < 9 {
+                // Walk `win_len` cells from `start`, giving up if we leave the
+                // board, or wrapping to the opposite edge.
+                let (mut r, mut c) = ((start / 3) as isize, (start % 3) as isize);
+                let mut mask = 0u16;
+                let mut cells = [0; 3];
+                let mut k = 0;
+                while k < win_len && r >= 0 && r < 3 && c >= 0 && c < 3 {
+                    mask |= 1 << (r * 3 + c);
+                    cells[k] = (r * 3 + c) as usize;
+                    r += dr;
+                    c += dc;
+                    if wrap {
+                        r = r.rem_euclid(3);
+                        c = c.rem_euclid(3);
+                    }
+                    k += 1;
+                }
+                if k == win_len && !lines.contains(mask) {
+                    lines.masks[lines.len] = mask;
+                    lines.cells[lines.len] = cells;
+                    lines.len += 1;
+                }
+                start += 1;
+            }
+            d += 1;
+        }
+        lines
+    }
+
+    /// Builds the lines of three cells listed in `lines`, without wrapping.
+    const fn from_cells(lines: &[[usize; 3]]) -> Self {
+        let mut win_lines = Self {
+            masks: [0; MAX_LINES],
+            cells: [[0; 3]; MAX_LINES],
+            len: lines.len(),
+            win_len: 3,
+            wrap: false,
+        };
+        let mut i = 0;
+        while i < lines.len() {
+            let [a, b, c] = lines[i];
+            win_lines.masks[i] = 1 << a | 1 << b | 1 << c;
+            win_lines.cells[i] = lines[i];
+            i += 1;
+        }
+        win_lines
+    }
+
+    /// Returns `true` if `mask` is already one of the lines.
+    const fn contains(&self, mask: u16) -> bool {
+        let mut i = 0;
+        while i < self.len {
+            if self.masks[i] == mask {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Returns the line masks.
+    pub(crate) fn masks(&self) -> &[u16] {
+        &self.masks[..self.len]
+    }
+
+    /// Returns the cell indices of the line at position `i` of [`WinLines::masks`].
+    pub(crate) fn cells(&self, i: usize) -> &[usize] {
+        &self.cells[i][..self.win_len]
+    }
+
+    /// Returns the number of marks in a row needed to win.
+    pub(crate) fn win_len(&self) -> usize {
+        self.win_len
+    }
+
+    /// Returns `true` if the lines wrap around the edges of the board.
+    pub(crate) fn wrap(&self) -> bool {
+        self.wrap
+    }
+}
+
+/// The winning lines of standard tic-tac-toe: three in a row, built from
+/// [`WINNING_LINES`] so that the two can't disagree.
+pub(crate) const STANDARD_LINES: WinLines = WinLines::from_cells(&WINNING_LINES);
+
+/// The 8 symmetries of the board (rotations and reflections), each mapping
+/// a cell index to the index it is moved to. Ordered like [`Symmetry::ALL`].
+///
+/// [`Symmetry::ALL`]: crate::Symmetry::ALL
+pub(crate) const SYMMETRIES: [[usize; 9]; 8] = [
+    // Identity
+    [0, 1, 2, 3, 4, 5, 6, 7, 8],
+    // Rotations by 90°, 180° and 270° clockwise
+    [2, 5, 8, 1, 4, 7, 0, 3, 6],
+    [8, 7, 6, 5, 4, 3, 2, 1, 0],
+    [6, 3, 0, 7, 4, 1, 8, 5, 2],
+    // Reflections across the vertical and horizontal axes
+    [2, 1, 0, 5, 4, 3, 8, 7, 6],
+    [6, 7, 8, 3, 4, 5, 0, 1, 2],
+    // Reflections across the main and anti diagonals
+    [0, 3, 6, 1, 4, 7, 2, 5, 8],
+    [8, 5, 2, 7, 4, 1, 6, 3, 0],
+];
+
+/// The state of a [`Bitboard`]. Unlike [`GameState`], it has no
+/// resignations or agreed draws: a board alone only ends in a win or a tie.
+///
+/// [`GameState`]: crate::GameState
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Outcome {
+    Win(Player),
+    Tie,
+    InProgress,
+}
+
+/// A compact board representation used by the AI search.
+///
+/// Each player's marks are stored as a `u16` bitmask where bit `i` is set if
+/// the player occupies cell `i`. Checking for a win then becomes a handful of
+/// bitwise operations against the [`WinLines`] masks instead of scanning the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Bitboard {
+    x: u16,
+    o: u16,
+    /// Cells no one can play. They are never empty and never hold a mark, so
+    /// no line through them can be completed.
+    blocked: u16,
+}
+
+impl Bitboard {
+    /// Converts a board of cells into its bitboard representation.
+    pub(crate) fn from_cells(board: &[Cell; 9]) -> Self {
+        let mut bitboard = Self {
+            x: 0,
+            o: 0,
+            blocked: 0,
+        };
+        for (i, cell) in board.iter().enumerate() {
+            match cell {
+                Cell::X => bitboard.x |= 1 << i,
+                Cell::O => bitboard.o |= 1 << i,
+                Cell::Empty => {}
+                Cell::Blocked => bitboard.blocked |= 1 << i,
+            }
+        }
+        bitboard
+    }
+
+    /// Returns the marks of the given player.
+    pub(crate) fn marks(&self, player: Player) -> u16 {
+        match player {
+            Player::X => self.x,
+            Player::O => self.o,
+        }
+    }
+
+    /// Returns a key uniquely identifying this board with `player` to move.
+    ///
+    /// Bits 0-8 hold X's marks, bits 9-17 O's marks and bit 18 is set when
+    /// O is to move. Blocked cells are left out, as they don't change during
+    /// a game.
+    pub(crate) fn key(&self, player: Player) -> u32 {
+        let side = match player {
+            Player::X => 0,
+            Player::O => 1,
+        };
+        self.x as u32 | (self.o as u32) << 9 | side << 18
+    }
+
+    /// Returns a mask of all empty cells.
+    pub(crate) fn empty(&self) -> u16 {
+        !(self.x | self.o | self.blocked) & FULL_MASK
+    }
+
+    /// Returns a copy of the bitboard with `player`'s mark placed at `index`.
+    pub(crate) fn with_move(mut self, index: usize, player: Player) -> Self {
+        match player {
+            Player::X => self.x |= 1 << index,
+            Player::O => self.o |= 1 << index,
+        }
+        self
+    }
+
+    /// Returns a mask of the empty cells that would complete a line for `player`.
+    pub(crate) fn winning_cells(&self, player: Player, lines: &WinLines) -> u16 {
+        let marks = self.marks(player);
+        let empty = self.empty();
+        lines.masks().iter().fold(0, |cells, &mask| {
+            // A line is one move from completion if exactly one of its cells
+            // is missing from the player's marks and that cell is empty.
+            let missing = mask & !marks;
+            if missing.count_ones() == 1 && missing & empty != 0 {
+                cells | missing
+            } else {
+                cells
+            }
+        })
+    }
+
+    /// Returns a mask of the empty cells where `player` would create a fork:
+    /// two or more simultaneous winning threats.
+    pub(crate) fn fork_cells(&self, player: Player, lines: &WinLines) -> u16 {
+        indices(self.empty())
+            .filter(|&i| {
+                let threats = self.with_move(i, player).winning_cells(player, lines);
+                threats.count_ones() >= 2
+            })
+            .fold(0, |cells, i| cells | (1 << i))
+    }
+
+    /// Returns the empty cells in the order the search should try them for
+    /// `player`: wins, then blocks of the opponent's wins, then the center,
+    /// corners and edges. Searching strong moves first lets alpha-beta pruning
+    /// cut off more of the tree.
+    pub(crate) fn ordered_moves(
+        &self,
+        player: Player,
+        lines: &WinLines,
+    ) -> impl Iterator<Item = usize> {
+        let wins = self.winning_cells(player, lines);
+        let blocks = self.winning_cells(player.opponent(), lines) & !wins;
+        let rest = self.empty() & !(wins | blocks);
+        indices(wins).chain(indices(blocks)).chain(
+            CELLS_BY_LINES
+                .into_iter()
+                .flat_map(move |cells| indices(rest & cells)),
+        )
+    }
+
+    /// Returns `true` if `player` has completed a winning line.
+    pub(crate) fn has_won(&self, player: Player, lines: &WinLines) -> bool {
+        let marks = self.marks(player);
+        // The player has won if no cell of a line is missing from their marks.
+        lines.masks().iter().any(|&mask| mask & !marks == 0)
+    }
+
+    /// Returns a copy of the bitboard with `symmetry` applied to every mark.
+    pub(crate) fn transformed(&self, symmetry: &[usize; 9]) -> Self {
+        let map = |marks: u16| indices(marks).fold(0, |mapped, i| mapped | (1 << symmetry[i]));
+        Self {
+            x: map(self.x),
+            o: map(self.o),
+            blocked: map(self.blocked),
+        }
+    }
+
+    /// Returns the symmetries that leave this board unchanged, including the identity.
+    pub(crate) fn symmetries(&self) -> impl Iterator<Item = &'static [usize; 9]> + '_ {
+        SYMMETRIES
+            .iter()
+            .filter(move |symmetry| self.transformed(symmetry) == *self)
+    }
+
+    /// Returns a mask of the empty cells that are distinct under the board's symmetries.
+    ///
+    /// When a rotation or reflection leaves the board unchanged, moves it maps
+    /// onto each other lead to equivalent positions with equal scores. Only the
+    /// lowest index of each such group is kept, so an empty board yields just
+    /// a corner, an edge and the center.
+    pub(crate) fn distinct_moves(&self) -> u16 {
+        let mut moves = self.empty();
+        for symmetry in self.symmetries() {
+            for i in indices(moves) {
+                if symmetry[i] < i {
+                    moves &= !(1 << i);
+                }
+            }
+        }
+        moves
+    }
+
+    /// Returns the lines still open to `player`: those without an opponent
+    /// mark or a blocked cell.
+    pub(crate) fn open_lines<'a>(
+        &self,
+        player: Player,
+        lines: &'a WinLines,
+    ) -> impl Iterator<Item = u16> + 'a {
+        let dead = self.marks(player.opponent()) | self.blocked;
+        lines
+            .masks()
+            .iter()
+            .copied()
+            .filter(move |&mask| mask & dead == 0)
+    }
+
+    /// Scores the lines still open to `player`: each is worth 10 to the power
+    /// of the number of `player`'s marks on it.
+    pub(crate) fn open_line_score(&self, player: Player, lines: &WinLines) -> i32 {
+        let own = self.marks(player);
+        self.open_lines(player, lines)
+            .map(|mask| 10i32.pow((mask & own).count_ones()))
+            .sum()
+    }
+
+    /// Returns `true` if some sequence of moves, starting with `player`, leads
+    /// to a win for either side. Both sides' moves are chosen freely, so this
+    /// is `false` only when the game must end in a tie however it is played.
+    pub(crate) fn win_reachable(&self, player: Player, lines: &WinLines) -> bool {
+        match self.state(lines) {
+            Outcome::Win(_) => true,
+            Outcome::Tie => false,
+            Outcome::InProgress => indices(self.empty()).any(|i| {
+                self.with_move(i, player)
+                    .win_reachable(player.opponent(), lines)
+            }),
+        }
+    }
+
+    /// Returns the number of positions reachable from this board with `player`
+    /// to move, not counting this one.
+    pub(crate) fn tree_size(&self, player: Player, lines: &WinLines) -> u64 {
+        if self.state(lines) != Outcome::InProgress {
+            return 0;
+        }
+        indices(self.empty())
+            .map(|i| {
+                1 + self
+                    .with_move(i, player)
+                    .tree_size(player.opponent(), lines)
+            })
+            .sum()
+    }
+
+    /// Returns the number of complete games that can be played from this board
+    /// with `player` to move. A finished board counts as one game.
+    pub(crate) fn game_count(&self, player: Player, lines: &WinLines) -> u64 {
+        if self.state(lines) != Outcome::InProgress {
+            return 1;
+        }
+        indices(self.empty())
+            .map(|i| {
+                self.with_move(i, player)
+                    .game_count(player.opponent(), lines)
+            })
+            .sum()
+    }
+
+    /// Returns the state of the game on this board.
+    pub(crate) fn state(&self, lines: &WinLines) -> Outcome {
+        if self.has_won(Player::X, lines) {
+            Outcome::Win(Player::X)
+        } else if self.has_won(Player::O, lines) {
+            Outcome::Win(Player::O)
+        } else if self.empty() == 0 {
+            Outcome::Tie
+        } else {
+            Outcome::InProgress
+        }
+    }
+}
+
+/// Iterates over the indices of the set bits of `mask`, lowest first.
+pub(crate) fn indices(mut mask: u16) -> impl Iterator<Item = usize> {
+    core::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+        let index = mask.trailing_zeros() as usize;
+        mask &= mask - 1;
+        Some(index)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_lines_for_each_win_length() {
+        let line = |cells: &[usize]| cells.iter().fold(0u16, |mask, &i| mask | (1 << i));
+        let standard: Vec<u16> = WINNING_LINES.iter().map(|cells| line(cells)).collect();
+        assert_eq!(STANDARD_LINES.masks(), standard);
+        for (i, cells) in WINNING_LINES.iter().enumerate() {
+            assert_eq!(STANDARD_LINES.cells(i), cells);
+        }
+        assert_eq!(WinLines::new(3, false), STANDARD_LINES);
+        assert_eq!(WinLines::new(2, false).masks().len(), 20);
+        assert!(WinLines::new(2, false).masks().contains(&line(&[1, 5])));
+        assert_eq!(WinLines::new(1, false).masks().len(), 9);
+
+        // Wrapping adds the broken diagonals, and pairs across the edges.
+        let torus = WinLines::new(3, true);
+        assert_eq!(torus.masks().len(), 12);
+        assert!(standard.iter().all(|mask| torus.masks().contains(mask)));
+        for cells in [[1, 5, 6], [2, 3, 7], [0, 5, 7], [1, 3, 8]] {
+            assert!(torus.masks().contains(&line(&cells)));
+        }
+        assert_eq!(WinLines::new(2, true).masks().len(), MAX_LINES);
+        assert!(WinLines::new(2, true).masks().contains(&line(&[0, 2])));
+        assert_eq!(WinLines::new(1, true).masks().len(), 9);
+    }
+
+    #[test]
+    fn converts_cells_to_bits() {
+        let mut board = [Cell::Empty; 9];
+        board[0] = Cell::X;
+        board[4] = Cell::O;
+        board[8] = Cell::X;
+        let bitboard = Bitboard::from_cells(&board);
+        assert_eq!(bitboard.marks(Player::X), 0b1_0000_0001);
+        assert_eq!(bitboard.marks(Player::O), 0b0_0001_0000);
+        assert_eq!(
+            indices(bitboard.empty()).collect::<Vec<_>>(),
+            [1, 2, 3, 5, 6, 7]
+        );
+    }
+
+    #[test]
+    fn distinct_moves_skip_symmetric_duplicates() {
+        let empty = Bitboard::from_cells(&[Cell::Empty; 9]);
+        assert_eq!(
+            indices(empty.distinct_moves()).collect::<Vec<_>>(),
+            [0, 1, 4]
+        );
+
+        // With X in the center the symmetries still hold.
+        let center = empty.with_move(4, Player::X);
+        assert_eq!(indices(center.distinct_moves()).collect::<Vec<_>>(), [0, 1]);
+
+        // X in a corner only leaves the main diagonal reflection.
+        let corner = empty.with_move(0, Player::X);
+        assert_eq!(
+            indices(corner.distinct_moves()).collect::<Vec<_>>(),
+            [1, 2, 4, 5, 8]
+        );
+    }
+
+    #[test]
+    fn finds_threats_and_forks() {
+        let board = Bitboard::from_cells(&[Cell::Empty; 9])
+            .with_move(0, Player::X)
+            .with_move(4, Player::O)
+            .with_move(8, Player::X);
+        assert_eq!(board.winning_cells(Player::X, &STANDARD_LINES), 0);
+        assert_eq!(
+            indices(board.fork_cells(Player::X, &STANDARD_LINES)).collect::<Vec<_>>(),
+            [2, 6]
+        );
+
+        let board = board.with_move(2, Player::X);
+        assert_eq!(
+            indices(board.winning_cells(Player::X, &STANDARD_LINES)).collect::<Vec<_>>(),
+            [1, 5]
+        );
+    }
+
+    #[test]
+    fn orders_moves_by_promise() {
+        let empty = Bitboard::from_cells(&[Cell::Empty; 9]);
+        assert_eq!(
+            empty
+                .ordered_moves(Player::X, &STANDARD_LINES)
+                .collect::<Vec<_>>(),
+            [4, 0, 2, 6, 8, 1, 3, 5, 7]
+        );
+
+        // X's win at 2 comes first, then the block at 3.
+        let board = empty
+            .with_move(0, Player::X)
+            .with_move(4, Player::O)
+            .with_move(1, Player::X)
+            .with_move(5, Player::O);
+        assert_eq!(
+            board
+                .ordered_moves(Player::X, &STANDARD_LINES)
+                .collect::<Vec<_>>(),
+            [2, 3, 6, 8, 7]
+        );
+    }
+
+    #[test]
+    fn detects_wins_with_masks() {
+        let bitboard = Bitboard::from_cells(&[Cell::Empty; 9])
+            .with_move(2, Player::O)
+            .with_move(4, Player::O);
+        assert_eq!(bitboard.state(&STANDARD_LINES), Outcome::InProgress);
+        assert_eq!(
+            bitboard.with_move(6, Player::O).state(&STANDARD_LINES),
+            Outcome::Win(Player::O)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use crate::bitboard::WinLines;
+use crate::game_engine::{DEFAULT_SEED, GameEngine};
+use crate::types::{Player, ScoringConfig, TieBreak};
+
+/// A builder for configuring a [`GameEngine`].
+///
+/// This is the recommended way to construct an engine. Every option has a
+/// default matching [`GameEngine::new`], so only the settings that differ
+/// need to be specified:
+///
+/// - AI **enabled**
+/// - `Player::X` moves first
+/// - AI picks the lowest index among equally good moves ([`TieBreak::LowestIndex`])
+/// - No handicap: the AI always plays an optimal move
+/// - Default [`ScoringConfig`]: the AI aims to win
+/// - Three in a row wins, without wrapping around the edges
+/// - No blocked cells
+///
+/// # Example
+/// ```
+/// use xo_core::{GameEngineBuilder, Player};
+///
+/// let game = GameEngineBuilder::new()
+///     .ai(true)
+///     .starting_player(Player::O)
+///     .seed(42)
+///     .build();
+///
+/// assert_eq!(game.current_player, Player::O);
+/// assert_eq!(game.seed(), 42);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameEngineBuilder {
+    ai_enabled: bool,
+    starting_player: Player,
+    seed: u64,
+    randomize_ties: bool,
+    tie_break: TieBreak,
+    handicap: f32,
+    scoring: ScoringConfig,
+    win_len: usize,
+    wrap: bool,
+    /// Mask of the cells to block.
+    blocked: u16,
+}
+
+impl Default for GameEngineBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameEngineBuilder {
+    /// Creates a builder with the default settings.
+    pub fn new() -> Self {
+        Self {
+            ai_enabled: true,
+            starting_player: Player::X,
+            seed: DEFAULT_SEED,
+            randomize_ties: false,
+            tie_break: TieBreak::default(),
+            handicap: 0.0,
+            scoring: ScoringConfig::default(),
+            win_len: 3,
+            wrap: false,
+            blocked: 0,
+        }
+    }
+
+    /// Sets whether the AI is enabled.
+    ///
+    /// - `true`: Single-player vs AI
+    /// - `false`: Human vs Human
+    pub fn ai(mut self, enabled: bool) -> Self {
+        self.ai_enabled = enabled;
+        self
+    }
+
+    /// Sets the player who makes the first move.
+    pub fn starting_player(mut self, player: Player) -> Self {
+        self.starting_player = player;
+        self
+    }
+
+    /// Sets the seed for the engine's random choices.
+    ///
+    /// Engines built with the same seed make the same choices, which keeps
+    /// games reproducible.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets whether the AI picks randomly among equally good moves.
+    ///
+    /// See [`GameEngine::randomize_ties`].
+    pub fn randomize_ties(mut self, enabled: bool) -> Self {
+        self.randomize_ties = enabled;
+        self
+    }
+
+    /// Sets how the AI chooses among equally good moves.
+    ///
+    /// See [`GameEngine::tie_break`].
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Sets how often the AI deliberately plays a weaker move, from `0.0`
+    /// (never, the default) to `1.0` (whenever it can).
+    ///
+    /// See [`GameEngine::handicap`].
+    ///
+    /// # Panics
+    /// Panics if `handicap` is not in `0.0..=1.0`.
+    pub fn handicap(mut self, handicap: f32) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&handicap),
+            "handicap must be between 0.0 and 1.0, got {}",
+            handicap
+        );
+        self.handicap = handicap;
+        self
+    }
+
+    /// Sets how the AI values a win, a loss and a tie.
+    ///
+    /// See [`GameEngine::scoring`].
+    pub fn scoring(mut self, scoring: ScoringConfig) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Sets the number of marks in a row needed to win, 3 by default.
+    ///
+    /// A line is any `win_len` consecutive cells along a row, column or
+    /// diagonal, so with `win_len = 2` any two adjacent marks in a straight
+    /// line win. Both win detection and the AI honor the setting. Shorter
+    /// lengths are mainly useful for teaching and for quick tests.
+    ///
+    /// # Panics
+    /// Panics if `win_len` is not in `1..=3`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::builder().win_len(2).build();
+    /// for m in [0, 8, 1] {
+    ///     game.make_move(m).unwrap();
+    /// }
+    /// assert_eq!(game.check_state(), GameState::Win(Player::X));
+    /// ```
+    pub fn win_len(mut self, win_len: usize) -> Self {
+        assert!(
+            (1..=3).contains(&win_len),
+            "win length must be between 1 and 3, got {}",
+            win_len
+        );
+        self.win_len = win_len;
+        self
+    }
+
+    /// Sets whether winning lines wrap around the edges of the board, as if
+    /// it were drawn on a torus.
+    ///
+    /// With the standard win length of 3, rows and columns already span the
+    /// board, so wrapping adds the four "broken diagonals":
+    ///
+    /// - `[1, 5, 6]` and `[2, 3, 7]`, parallel to the diagonal `[0, 4, 8]`
+    /// - `[0, 5, 7]` and `[1, 3, 8]`, parallel to the anti-diagonal `[2, 4, 6]`
+    ///
+    /// With a shorter [`win_len`](Self::win_len), lines also run across the
+    /// edges, e.g. `[2, 0]` wins with `win_len = 2`. Win detection, the
+    /// winning line, [`GameEngine::completed_lines`],
+    /// [`GameEngine::dead_lines`] and the AI all use the extra lines.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::builder().wrap(true).build();
+    /// game.simulate_moves(&[1, 0, 5, 2, 6]).unwrap();
+    /// assert_eq!(game.check_state(), GameState::Win(Player::X));
+    /// assert_eq!(game.winning_line(), Some(&[1, 5, 6][..]));
+    /// ```
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets cells that no one can play, for variant boards with holes.
+    ///
+    /// The cells hold [`Cell::Blocked`](crate::Cell::Blocked): moves there are
+    /// rejected with [`MoveError::CellBlocked`](crate::MoveError::CellBlocked),
+    /// the AI never considers them, and no line through them can be won.
+    /// Replaces any cells blocked by an earlier call.
+    ///
+    /// # Panics
+    /// Panics if an index is not in `0..9`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::builder().blocked_cells(&[4]).build();
+    /// assert_eq!(game.get_board()[4], Cell::Blocked);
+    /// assert_eq!(game.make_move(4), Err(MoveError::CellBlocked));
+    /// ```
+    pub fn blocked_cells(mut self, cells: &[usize]) -> Self {
+        self.blocked = cells.iter().fold(0, |mask, &index| {
+            assert!(
+                index < 9,
+                "blocked cell index must be in 0..9, got {}",
+                index
+            );
+            mask | 1 << index
+        });
+        self
+    }
+
+    /// Builds the configured [`GameEngine`] with an empty board.
+    pub fn build(self) -> GameEngine {
+        let mut game = GameEngine::with_starting_player(self.starting_player, self.ai_enabled);
+        game.reseed(self.seed);
+        game.randomize_ties = self.randomize_ties;
+        game.tie_break = self.tie_break;
+        game.handicap = self.handicap;
+        game.scoring = self.scoring;
+        game.lines = WinLines::new(self.win_len, self.wrap);
+        game.block_cells(self.blocked);
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Cell;
+
+    #[test]
+    fn defaults_match_new() {
+        let built = GameEngineBuilder::new().build();
+        let game = GameEngine::new();
+        assert_eq!(built.current_player, game.current_player);
+        assert_eq!(built.ai_enabled, game.ai_enabled);
+        assert_eq!(built.seed(), game.seed());
+        assert_eq!(built.win_len(), 3);
+        assert!(built.get_board().iter().all(|&c| c == Cell::Empty));
+    }
+
+    #[test]
+    fn options_are_applied() {
+        let game = GameEngine::builder()
+            .ai(false)
+            .starting_player(Player::O)
+            .seed(7)
+            .build();
+        assert!(!game.ai_enabled);
+        assert_eq!(game.current_player, Player::O);
+        assert_eq!(game.seed(), 7);
+    }
+}
@@ -1,4 +1,51 @@
-use crate::types::{Cell, GameState, MoveError, Player};
+use crate::bitboard::{self, Bitboard, Outcome, STANDARD_LINES, WinLines};
+use crate::builder::GameEngineBuilder;
+use crate::rng::Rng;
+use crate::strategy::{Minimax, Random, Strategy};
+use crate::transposition::{Bound, Entry, TranspositionTable};
+#[cfg(feature = "alloc")]
+use crate::types::GameResult;
+use crate::types::{
+    BoardError, Cell, CellKind, GamePhase, GameRecord, GameSnapshot, GameState, Hint, HintCategory,
+    MoveError, MoveToken, NotationError, ParseMoveError, Player, ScoringConfig, Symmetry, TieBreak,
+};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+use core::fmt;
+
+/// The seed used for the engine's random choices when none is configured.
+pub(crate) const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The search depth that never cuts the search off, for perfect play.
+const UNLIMITED_DEPTH: usize = usize::MAX;
+
+/// The eight winning lines of standard tic-tac-toe, as cell indices.
+///
+/// Listed as the rows, then the columns, then the two diagonals, with the
+/// cells of each line in board order. These are the lines
+/// [`GameEngine::check_state`] checks when three in a row wins; engines with a
+/// shorter [`win_len`](GameEngine::win_len) generate their own.
+///
+/// # Example
+/// ```
+/// use xo_core::WINNING_LINES;
+///
+/// let through_center = WINNING_LINES.iter().filter(|line| line.contains(&4)).count();
+/// assert_eq!(through_center, 4);
+/// ```
+pub const WINNING_LINES: [[usize; 3]; 8] = [
+    // Rows
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    // Columns
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    // Diagonals
+    [0, 4, 8],
+    [2, 4, 6],
+];
 
 /// The core Tic-Tac-Toe game engine.
 ///
@@ -22,6 +69,10 @@ use crate::types::{Cell, GameState, MoveError, Player};
 /// - **Human vs AI:** calls [`make_move`], then queries
 ///   [`get_best_move`] to find the AI’s move and applies it with [`make_move`].
 ///
+/// # Construction
+/// [`GameEngine::new`] and [`GameEngine::with_ai`] cover the common cases.
+/// For anything else, [`GameEngine::builder`] is the recommended path.
+///
 /// # Examples
 ///
 /// ## Human vs Human
@@ -44,12 +95,14 @@ use crate::types::{Cell, GameState, MoveError, Player};
 ///
 /// game.make_move(0).unwrap(); // Human plays top-left
 ///
-/// if let Some(ai_move) = game.get_best_move() {
-///     game.make_move(ai_move).unwrap(); // Apply AI move
-/// }
+/// let ai_move = game.make_ai_move(); // AI replies
+/// assert_eq!(ai_move, Some(4));
 /// ```
+#[derive(Clone)]
 pub struct GameEngine {
     board: [Cell; 9],
+    /// The state of `board`, kept up to date as moves are made and undone.
+    state: GameState,
     /// The player whose turn it is.
     pub current_player: Player,
     /// Whether the AI is enabled.
@@ -57,6 +110,97 @@ pub struct GameEngine {
     /// - `true`: Single-player vs AI
     /// - `false`: Human vs Human
     pub ai_enabled: bool,
+    /// Whether the AI picks randomly among equally good moves.
+    ///
+    /// - `true`: A pseudo-random optimal move is chosen using the engine's [`seed`](GameEngine::seed).
+    /// - `false`: The optimal move with the lowest index is chosen (the default).
+    ///
+    /// Either way the AI stays unbeatable; randomizing just makes it less
+    /// predictable across games. The choice depends only on the seed and the
+    /// position, so repeated calls in the same position agree with each other.
+    pub randomize_ties: bool,
+    /// How the AI chooses among equally good moves, the lowest index by default.
+    ///
+    /// Setting [`randomize_ties`](GameEngine::randomize_ties) overrides this
+    /// with [`TieBreak::Random`].
+    pub tie_break: TieBreak,
+    /// How the AI values a win, a loss and a tie.
+    ///
+    /// The default scores of `10`, `-10` and `0` give perfect play. Other
+    /// values change which outcomes the AI aims for, e.g. a tie scored above
+    /// a win makes it settle for draws.
+    pub scoring: ScoringConfig,
+    /// How often the AI deliberately plays a weaker move, from `0.0` to `1.0`.
+    ///
+    /// With probability `handicap`, [`get_best_move`](GameEngine::get_best_move)
+    /// picks a pseudo-random non-optimal move instead of an optimal one, if
+    /// there is one. `0.0` (the default) keeps the AI unbeatable, while `1.0`
+    /// makes it miss every win and block it can. Like `randomize_ties`, the
+    /// choice depends only on the seed and the position, so it is reproducible.
+    pub handicap: f32,
+    /// Every move played so far, followed by moves that were undone and can be redone.
+    history: [usize; 9],
+    /// The number of moves currently played.
+    history_len: usize,
+    /// The number of moves recorded in `history`, including undone ones.
+    recorded_len: usize,
+    /// The seed for the engine's random choices.
+    seed: u64,
+    /// Generator for random moves, seeded from `seed`.
+    rng: Rng,
+    /// The lines that win the game, generated from the win length.
+    pub(crate) lines: WinLines,
+    /// The characters the `Display` impl draws for X, O and empty cells.
+    symbols: [char; 3],
+    /// Called after every move is applied.
+    #[cfg(feature = "alloc")]
+    on_move: OnMove,
+}
+
+/// A callback invoked with the player who moved and the index they played.
+#[cfg(feature = "alloc")]
+type MoveCallback = Box<dyn FnMut(Player, usize) + Send>;
+
+/// Holds the optional move callback of a [`GameEngine`].
+///
+/// Callbacks can't be cloned, so a cloned engine starts without one.
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct OnMove(Option<MoveCallback>);
+
+#[cfg(feature = "alloc")]
+impl Clone for OnMove {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+/// The state of a single minimax search.
+#[derive(Debug)]
+struct Search {
+    /// The player whose point of view scores are given from.
+    maximizer: Player,
+    /// Evaluations of positions already searched.
+    table: TranspositionTable,
+    /// The number of positions visited so far.
+    nodes: u64,
+}
+
+impl Search {
+    /// Starts a search scoring positions from `maximizer`'s point of view.
+    fn new(maximizer: Player) -> Self {
+        Self {
+            maximizer,
+            table: TranspositionTable::new(),
+            nodes: 0,
+        }
+    }
+}
+
+impl Default for GameEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameEngine {
@@ -75,11 +219,7 @@ impl GameEngine {
     /// assert!(game.ai_enabled);
     /// ```
     pub fn new() -> Self {
-        Self {
-            board: [Cell::Empty; 9],
-            current_player: Player::X,
-            ai_enabled: true,
-        }
+        Self::with_ai(true)
     }
 
     /// Creates a new instance of the game engine with an option to disable AI.
@@ -99,338 +239,5392 @@ impl GameEngine {
     pub fn with_ai(ai_enabled: bool) -> Self {
         Self {
             board: [Cell::Empty; 9],
+            state: GameState::InProgress,
             current_player: Player::X,
             ai_enabled,
+            randomize_ties: false,
+            tie_break: TieBreak::default(),
+            handicap: 0.0,
+            scoring: ScoringConfig::default(),
+            seed: DEFAULT_SEED,
+            rng: Rng::new(DEFAULT_SEED),
+            lines: STANDARD_LINES,
+            history: [0; 9],
+            history_len: 0,
+            recorded_len: 0,
+            symbols: ['X', 'O', '.'],
+            #[cfg(feature = "alloc")]
+            on_move: OnMove::default(),
         }
     }
 
-    /// Returns a reference to the current board.
-    pub fn get_board(&self) -> &[Cell; 9] {
-        &self.board
-    }
-
-    /// Attempts to make a move for the current player at the given board index.
+    /// Creates a new instance of the game engine where `player` moves first.
     ///
-    /// # Parameters
-    /// - `index`: The 0-based cell index (0–8).
-    ///
-    /// # Returns
-    /// - `Ok(())` if the move was made successfully.
-    /// - `Err(MoveError)` if the move is invalid:
-    ///   - `MoveError::OutOfBounds` if `index >= 9`
-    ///   - `MoveError::CellOccupied` if the cell already has a mark
+    /// This is useful for alternating who opens across rounds.
     ///
     /// # Example
     /// ```
-    /// use xo_core::{GameEngine, MoveError};
+    /// use xo_core::{GameEngine, Player};
     ///
-    /// let mut game = GameEngine::new();
-    /// assert!(game.make_move(0).is_ok());
-    /// assert_eq!(game.make_move(0), Err(MoveError::CellOccupied));
+    /// let mut game = GameEngine::with_starting_player(Player::O, false);
+    /// assert_eq!(game.current_player, Player::O);
+    ///
+    /// game.make_move(4).unwrap(); // O opens in the center
+    /// assert_eq!(game.current_player, Player::X);
     /// ```
-    pub fn make_move(&mut self, index: usize) -> Result<(), MoveError> {
-        // First, check if the index is within the valid range of the board.
-        if index >= 9 {
-            return Err(MoveError::OutOfBounds);
-        }
-
-        // Then, check if the cell is already occupied.
-        if self.board[index] != Cell::Empty {
-            return Err(MoveError::CellOccupied);
-        }
-
-        // Place the current player's mark on the board.
-        match self.current_player {
-            Player::X => self.board[index] = Cell::X,
-            Player::O => self.board[index] = Cell::O,
+    pub fn with_starting_player(player: Player, ai_enabled: bool) -> Self {
+        Self {
+            current_player: player,
+            ..Self::with_ai(ai_enabled)
         }
-
-        // Switch to the other player for the next turn.
-        self.current_player = self.current_player.opponent();
-        Ok(())
     }
 
-    /// Returns the current state of the game.
+    /// Creates a game engine from an existing board, with AI **enabled**.
     ///
-    /// Possible values:
-    /// - `GameState::InProgress`
-    /// - `GameState::Tie`
-    /// - `GameState::Won(Player::X)`
-    /// - `GameState::Won(Player::O)`
-    pub fn check_state(&self) -> GameState {
-        Self::check_board_state(&self, self.board)
+    /// The board must be reachable in a standard game where X moves first:
+    /// X has as many marks as O or exactly one more, at most one player has
+    /// completed a line, and no move follows a win. The player to move is
+    /// inferred from the mark counts.
+    ///
+    /// The engine has no record of how the board was reached, so its move
+    /// history starts empty.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{BoardError, Cell, GameEngine, Player};
+    ///
+    /// let mut board = [Cell::Empty; 9];
+    /// board[4] = Cell::X;
+    ///
+    /// let game = GameEngine::from_board(board).unwrap();
+    /// assert_eq!(game.current_player, Player::O);
+    ///
+    /// board[0] = Cell::X;
+    /// assert_eq!(
+    ///     GameEngine::from_board(board).err(),
+    ///     Some(BoardError::InvalidMarkCounts { x: 2, o: 0 })
+    /// );
+    /// ```
+    pub fn from_board(board: [Cell; 9]) -> Result<Self, BoardError> {
+        Self::from_board_opened_by(board, Player::X)
     }
 
-    /// Returns `true` if the game is finished (either win or draw).
-    pub fn is_over(&self) -> bool {
-        !matches!(self.check_state(), GameState::InProgress)
+    /// Like [`GameEngine::from_board`], but for a game where `first` moved first.
+    fn from_board_opened_by(board: [Cell; 9], first: Player) -> Result<Self, BoardError> {
+        let mut game = Self::new();
+        game.current_player = Self::validate(&board, &game.lines, first)?;
+        game.board = board;
+        game.state = game.check_board_state(board);
+        Ok(game)
     }
 
-    /// Calculates the best move for the current player using Minimax with pruning.
+    /// Checks that `board` could be reached in a standard game where X moves first.
     ///
-    /// Returns:
-    /// - `Some(index)` for the best move when AI is enabled.
-    /// - `None` if the game is over or AI is disabled.
+    /// These are the checks [`GameEngine::from_board`] performs, so untrusted
+    /// board data can be validated without building an engine.
     ///
-    /// # Example
-    /// ```
-    /// use xo_core::GameEngine;
+    /// # Errors
+    /// - `BoardError::InvalidMarkCounts` if X doesn't have as many marks as O
+    ///   or exactly one more.
+    /// - `BoardError::MultipleWinners` if both players have completed a line.
+    /// - `BoardError::MoveAfterWin` if a player has won but the mark counts
+    ///   show the other player moved afterwards.
     ///
-    /// let game = GameEngine::with_ai(false);
-    /// assert_eq!(game.get_best_move(), None); // AI disabled
-    /// ```
-    /// # Example with AI
+    /// # Example
     /// ```
-    /// use xo_core::GameEngine;
+    /// use xo_core::{BoardError, Cell, GameEngine};
     ///
-    /// let mut game = GameEngine::new();
-    /// game.make_move(0).unwrap(); // X
-    /// game.make_move(4).unwrap(); // O
-    /// game.make_move(1).unwrap(); // X
+    /// let (x, o, e) = (Cell::X, Cell::O, Cell::Empty);
+    /// assert_eq!(GameEngine::validate_board(&[x, o, e, e, e, e, e, e, e]), Ok(()));
     ///
-    /// // O should block X's winning move at index 2
-    /// assert_eq!(game.get_best_move(), Some(2));
+    /// // Both players have completed a row.
+    /// assert_eq!(
+    ///     GameEngine::validate_board(&[x, x, x, o, o, o, e, e, e]),
+    ///     Err(BoardError::MultipleWinners)
+    /// );
+    /// // X won with the top row, yet O moved afterwards.
+    /// assert_eq!(
+    ///     GameEngine::validate_board(&[x, x, x, o, o, e, o, e, e]),
+    ///     Err(BoardError::MoveAfterWin)
+    /// );
     /// ```
-    ///
-    pub fn get_best_move(&self) -> Option<usize> {
-        // If the game is over, no move can be made.
-        // furthermore, if AI is disabled, return None.
-        if !self.ai_enabled || self.is_over() {
-            return None;
-        }
-
-        let mut best_score = -i32::MAX;
-        let mut best_move: Option<usize> = None;
-
-        // The current player is the maximizing player for the Minimax algorithm.
-        let maximizing_player = self.current_player;
-
-        // Iterate through each cell on the board.
-        for i in 0..9 {
-            // Only consider empty cells as potential moves.
-            if self.board[i] == Cell::Empty {
-                // Create a temporary clone of the board to simulate the move.
-                let mut temp_board = self.board;
-                match maximizing_player {
-                    Player::X => temp_board[i] = Cell::X,
-                    Player::O => temp_board[i] = Cell::O,
-                }
+    pub fn validate_board(board: &[Cell; 9]) -> Result<(), BoardError> {
+        Self::validate(board, &STANDARD_LINES, Player::X).map(|_| ())
+    }
 
-                // Recursively call the minimax function to evaluate the score of this move.
-                let score = self.minimax_with_pruning(
-                    temp_board,
-                    maximizing_player.opponent(),
-                    -i32::MAX,
-                    i32::MAX,
-                );
+    /// Checks that `board` is reachable in a game where `first` moves first,
+    /// returning the player to move.
+    fn validate(board: &[Cell; 9], lines: &WinLines, first: Player) -> Result<Player, BoardError> {
+        let (x, o) = Self::count_marks(board);
+        let to_move =
+            Self::player_to_move(x, o, first).ok_or(BoardError::InvalidMarkCounts { x, o })?;
 
-                // If this move's score is better than the current best score,
-                // update the best score and the best move index.
-                if score > best_score {
-                    best_score = score;
-                    best_move = Some(i);
-                }
-            }
+        let bitboard = Bitboard::from_cells(board);
+        match (
+            bitboard.has_won(Player::X, lines),
+            bitboard.has_won(Player::O, lines),
+        ) {
+            (true, true) => Err(BoardError::MultipleWinners),
+            // The winner made the last move, so it's their opponent's turn.
+            (true, false) if to_move != Player::O => Err(BoardError::MoveAfterWin),
+            (false, true) if to_move != Player::X => Err(BoardError::MoveAfterWin),
+            _ => Ok(to_move),
         }
-        best_move
     }
 
-    /// The Minimax algorithm with Alpha-Beta pruning, implemented recursively.
+    /// Returns `true` if it can be `current_player`'s turn on `board`, going
+    /// by the mark counts alone.
     ///
-    /// This is a private helper method that evaluates the game tree to find the
-    /// best possible move.
-    /// - `board`: The current state of the game board.
-    /// - `player`: The player whose turn it is to evaluate.
-    /// - `alpha`: The best score for the maximizing player.
-    /// - `beta`: The best score for the minimizing player.
+    /// X moves first, so it is X's turn when both players have the same
+    /// number of marks and O's turn when X has one more. Any other counts are
+    /// inconsistent for both players. [`GameEngine::from_board`] infers the
+    /// player to move by the same rule, so this checks a turn declared by
+    /// untrusted data before relying on it.
     ///
-    /// Returns an integer score for the current board state.
-    fn minimax_with_pruning(
-        &self,
-        board: [Cell; 9],
-        player: Player,
-        mut alpha: i32,
-        mut beta: i32,
-    ) -> i32 {
-        // Check the state of the board and return a score if the game is over.
-        let state = self.check_board_state(board);
-        match state {
-            GameState::Win(winner) => {
-                // Return a positive score for a win, negative for a loss.
-                // The score is large to represent a definite win/loss.
-                return if winner == self.current_player {
-                    10
-                } else {
-                    -10
-                };
-            }
-            GameState::Tie => return 0,
-            GameState::InProgress => {}
-        }
-
-        // Find all available moves (empty cells).
-        let available_moves: Vec<usize> = board
-            .iter()
-            .enumerate()
-            .filter_map(
-                |(i, &cell)| {
-                    if cell == Cell::Empty { Some(i) } else { None }
-                },
-            )
-            .collect();
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine, Player};
+    ///
+    /// let mut board = [Cell::Empty; 9];
+    /// assert!(GameEngine::turn_is_consistent(&board, Player::X));
+    ///
+    /// board[4] = Cell::X;
+    /// assert!(GameEngine::turn_is_consistent(&board, Player::O));
+    /// assert!(!GameEngine::turn_is_consistent(&board, Player::X));
+    /// ```
+    pub fn turn_is_consistent(board: &[Cell; 9], current_player: Player) -> bool {
+        let (x, o) = Self::count_marks(board);
+        Self::player_to_move(x, o, Player::X) == Some(current_player)
+    }
 
-        // If there are no available moves, it's a tie.
-        if available_moves.is_empty() {
-            return 0;
+    /// Returns the player to move when X has `x` marks, O has `o` and `first`
+    /// moved first, or `None` if the counts can't occur.
+    fn player_to_move(x: usize, o: usize, first: Player) -> Option<Player> {
+        let (first_marks, second_marks) = match first {
+            Player::X => (x, o),
+            Player::O => (o, x),
+        };
+        // The first player is level with the second or one mark ahead.
+        if first_marks == second_marks {
+            Some(first)
+        } else if first_marks == second_marks + 1 {
+            Some(first.opponent())
+        } else {
+            None
         }
+    }
 
-        // The current player is either the maximizing or minimizing player in this subtree.
-        let current_player_is_maximizing = player == self.current_player;
-
-        if current_player_is_maximizing {
-            let mut max_eval = -i32::MAX;
-            for &move_index in &available_moves {
-                // Simulate the move.
-                let mut temp_board = board;
-                match player {
-                    Player::X => temp_board[move_index] = Cell::X,
-                    Player::O => temp_board[move_index] = Cell::O,
-                }
+    /// Returns the number of X and O marks on `board`, in that order.
+    fn count_marks(board: &[Cell; 9]) -> (usize, usize) {
+        let count = |mark| board.iter().filter(|&&cell| cell == mark).count();
+        (count(Cell::X), count(Cell::O))
+    }
 
-                // Recursively call minimax for the opponent.
-                let eval = self.minimax_with_pruning(temp_board, player.opponent(), alpha, beta);
+    /// Returns a [`GameEngineBuilder`] for configuring a new engine.
+    ///
+    /// This is the recommended way to construct an engine with anything
+    /// other than the default settings.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let game = GameEngine::builder()
+    ///     .ai(false)
+    ///     .starting_player(Player::O)
+    ///     .build();
+    /// assert_eq!(game.current_player, Player::O);
+    /// assert!(!game.ai_enabled);
+    /// ```
+    pub fn builder() -> GameEngineBuilder {
+        GameEngineBuilder::new()
+    }
 
-                // Update the maximum score.
-                max_eval = max_eval.max(eval);
+    /// Returns whether the AI is enabled; the same as the
+    /// [`ai_enabled`](GameEngine::ai_enabled) field.
+    pub fn ai_enabled(&self) -> bool {
+        self.ai_enabled
+    }
 
-                // Update alpha for the maximizing player.
-                alpha = alpha.max(eval);
+    /// Enables or disables the AI, at any point in the game.
+    ///
+    /// This is equivalent to assigning the public field, which remains for
+    /// compatibility, but keeps working should the engine ever need to react
+    /// to the change.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::with_ai(false);
+    /// game.make_move(4).unwrap();
+    /// assert_eq!(game.get_best_move(), None);
+    ///
+    /// // Hand O over to the AI mid-game.
+    /// game.set_ai_enabled(true);
+    /// assert!(game.ai_enabled());
+    /// assert_eq!(game.get_best_move(), Some(0));
+    /// ```
+    pub fn set_ai_enabled(&mut self, enabled: bool) {
+        self.ai_enabled = enabled;
+    }
 
-                // Alpha-beta pruning condition.
-                if beta <= alpha {
-                    break;
-                }
-            }
-            max_eval
-        } else {
-            let mut min_eval = i32::MAX;
-            for &move_index in &available_moves {
-                // Simulate the move.
-                let mut temp_board = board;
-                match player {
-                    Player::X => temp_board[move_index] = Cell::X,
-                    Player::O => temp_board[move_index] = Cell::O,
-                }
+    /// Returns the seed used for the engine's random choices.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
 
-                // Recursively call minimax for the opponent.
-                let eval = self.minimax_with_pruning(temp_board, player.opponent(), alpha, beta);
+    /// Sets the seed and restarts the random number generator from it.
+    pub(crate) fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Rng::new(seed);
+    }
 
-                // Update the minimum score.
-                min_eval = min_eval.min(eval);
+    /// Returns the number of marks in a row needed to win.
+    ///
+    /// This is 3 for standard tic-tac-toe. Shorter lengths can be configured
+    /// with [`GameEngineBuilder::win_len`].
+    pub fn win_len(&self) -> usize {
+        self.lines.win_len()
+    }
 
-                // Update beta for the minimizing player.
-                beta = beta.min(eval);
+    /// Returns `true` if winning lines wrap around the edges of the board,
+    /// as configured with [`GameEngineBuilder::wrap`].
+    pub fn wraps(&self) -> bool {
+        self.lines.wrap()
+    }
 
-                // Alpha-beta pruning condition.
-                if beta <= alpha {
-                    break;
-                }
-            }
-            min_eval
+    /// Classifies the cell at `index` as the center, a corner or an edge.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{CellKind, GameEngine};
+    ///
+    /// assert_eq!(GameEngine::cell_kind(4), Some(CellKind::Center));
+    /// assert_eq!(GameEngine::cell_kind(6), Some(CellKind::Corner));
+    /// assert_eq!(GameEngine::cell_kind(7), Some(CellKind::Edge));
+    /// assert_eq!(GameEngine::cell_kind(9), None);
+    /// ```
+    pub fn cell_kind(index: usize) -> Option<CellKind> {
+        match index {
+            4 => Some(CellKind::Center),
+            0 | 2 | 6 | 8 => Some(CellKind::Corner),
+            1 | 3 | 5 | 7 => Some(CellKind::Edge),
+            _ => None,
         }
     }
 
-    /// A helper function to check the state of a given board.
-    /// This is used internally by the Minimax algorithm.
-    fn check_board_state(&self, board: [Cell; 9]) -> GameState {
-        // Define all possible winning combinations (rows, columns, diagonals).
-        let winning_combinations = [
-            // Rows
-            [0, 1, 2],
-            [3, 4, 5],
-            [6, 7, 8],
-            // Columns
-            [0, 3, 6],
-            [1, 4, 7],
-            [2, 5, 8],
-            // Diagonals
-            [0, 4, 8],
-            [2, 4, 6],
-        ];
+    /// Returns the [`WINNING_LINES`] that pass through the cell at `index`.
+    ///
+    /// The center lies on 4 lines, each corner on 3 and each edge on 2.
+    /// Returns an empty `Vec` if `index` is out of bounds.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// assert_eq!(GameEngine::lines_through(1), vec![[0, 1, 2], [1, 4, 7]]);
+    /// assert_eq!(GameEngine::lines_through(4).len(), 4);
+    /// assert!(GameEngine::lines_through(9).is_empty());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn lines_through(index: usize) -> Vec<[usize; 3]> {
+        WINNING_LINES
+            .iter()
+            .filter(|line| line.contains(&index))
+            .copied()
+            .collect()
+    }
+
+    /// Sets the characters used to draw X, O and empty cells when the engine is
+    /// displayed.
+    ///
+    /// The defaults are `X`, `O` and `.`, matching the `Display` impl of [`Cell`].
+    /// Blocked cells are always drawn as `#`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    /// assert_eq!(game.to_string(), "O..\n.X.\n...");
+    ///
+    /// game.set_symbols('x', 'o', '-');
+    /// assert_eq!(game.to_string(), "o--\n-x-\n---");
+    /// ```
+    pub fn set_symbols(&mut self, x: char, o: char, empty: char) {
+        self.symbols = [x, o, empty];
+    }
 
-        // Iterate through each winning combination to check for a win.
-        for combination in &winning_combinations {
-            let cell_1 = board[combination[0]];
-            let cell_2 = board[combination[1]];
-            let cell_3 = board[combination[2]];
-
-            // If the cells are not empty and all three are the same, we have a winner.
-            if cell_1 != Cell::Empty && cell_1 == cell_2 && cell_2 == cell_3 {
-                // Determine the winning player based on the cell's state.
-                return match cell_1 {
-                    Cell::X => GameState::Win(Player::X),
-                    Cell::O => GameState::Win(Player::O),
-                    _ => unreachable!(),
+    /// Draws the board in a box with Unicode borders, for terminal apps.
+    ///
+    /// Marks are drawn with the characters chosen with
+    /// [`GameEngine::set_symbols`]. With `labels`, empty cells show their index
+    /// to guide input; otherwise they show the empty symbol. Blocked cells
+    /// show `#`. Rows are separated by newlines, with no trailing newline.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     game.to_ascii_art(true),
+    ///     "┌───┬───┬───┐\n\
+    ///      │ O │ 1 │ 2 │\n\
+    ///      ├───┼───┼───┤\n\
+    ///      │ 3 │ X │ 5 │\n\
+    ///      ├───┼───┼───┤\n\
+    ///      │ 6 │ 7 │ 8 │\n\
+    ///      └───┴───┴───┘"
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_ascii_art(&self, labels: bool) -> String {
+        let [x, o, empty] = self.symbols;
+        let mut art = String::from("┌───┬───┬───┐\n");
+        for (row, cells) in self.board.chunks(3).enumerate() {
+            if row > 0 {
+                art.push_str("├───┼───┼───┤\n");
+            }
+            art.push('│');
+            for (col, cell) in cells.iter().enumerate() {
+                let symbol = match cell {
+                    Cell::X => x,
+                    Cell::O => o,
+                    // Indices are single digits, so a label fits a cell.
+                    Cell::Empty if labels => char::from(b'0' + (row * 3 + col) as u8),
+                    Cell::Empty => empty,
+                    Cell::Blocked => '#',
                 };
+                art.push(' ');
+                art.push(symbol);
+                art.push_str(" │");
             }
+            art.push('\n');
         }
+        art.push_str("└───┴───┴───┘");
+        art
+    }
 
-        // If no winner is found, check if the board is full.
-        if !board.iter().any(|&cell| cell == Cell::Empty) {
-            return GameState::Tie;
-        }
-
-        // If neither a win nor a tie, the game is still ongoing.
-        GameState::InProgress
+    /// Returns a reference to the current board.
+    pub fn get_board(&self) -> &[Cell; 9] {
+        &self.board
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Returns a copy of the current board.
+    ///
+    /// Unlike [`GameEngine::get_board`], the copy doesn't borrow the engine,
+    /// so the engine can be changed while the copy is still in use.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine};
+    ///
+    /// let mut game = GameEngine::new();
+    /// let before = game.board_copy();
+    /// game.make_move(4).unwrap();
+    ///
+    /// assert_eq!(before[4], Cell::Empty);
+    /// assert_eq!(game.board_copy()[4], Cell::X);
+    /// ```
+    pub fn board_copy(&self) -> [Cell; 9] {
+        self.board
+    }
+
+    /// Returns the board as a row-major 3x3 grid.
+    ///
+    /// `grid[row][col]` corresponds to board index `row * 3 + col`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Cell};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(5).unwrap(); // X plays middle-right
+    ///
+    /// let grid = game.get_grid();
+    /// assert_eq!(grid[1][2], Cell::X);
+    /// ```
+    pub fn get_grid(&self) -> [[Cell; 3]; 3] {
+        let mut grid = [[Cell::Empty; 3]; 3];
+        for (i, &cell) in self.board.iter().enumerate() {
+            grid[i / 3][i % 3] = cell;
+        }
+        grid
+    }
+
+    /// Returns a copy of the board transformed by `symmetry`.
+    ///
+    /// The engine itself is not changed. This is useful for symmetry analysis
+    /// and for augmenting training data with equivalent positions.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine, Symmetry};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(1).unwrap(); // X plays top-middle
+    ///
+    /// let flipped = game.transform(Symmetry::FlipVertical);
+    /// assert_eq!(flipped[7], Cell::X); // Now bottom-middle
+    /// assert_eq!(game.get_board()[1], Cell::X);
+    /// ```
+    pub fn transform(&self, symmetry: Symmetry) -> [Cell; 9] {
+        symmetry.apply(&self.board)
+    }
+
+    /// Returns the canonical form of the board: the lexicographically smallest
+    /// of its 8 symmetric transforms (see [`Symmetry`]).
+    ///
+    /// Positions that are rotations or reflections of each other share the
+    /// same canonical form, so it can be used to deduplicate positions in
+    /// sets and tables.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut top_left = GameEngine::new();
+    /// top_left.make_move(0).unwrap();
+    ///
+    /// let mut bottom_right = GameEngine::new();
+    /// bottom_right.make_move(8).unwrap();
+    ///
+    /// assert_eq!(top_left.canonical_form(), bottom_right.canonical_form());
+    /// ```
+    pub fn canonical_form(&self) -> [Cell; 9] {
+        Symmetry::ALL
+            .iter()
+            .map(|&symmetry| self.transform(symmetry))
+            .min()
+            .unwrap_or(self.board)
+    }
+
+    /// Returns an iterator over the board's cells paired with their indices, in order.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Cell};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    ///
+    /// let taken: Vec<usize> = game
+    ///     .cells()
+    ///     .filter(|&(_, cell)| cell != Cell::Empty)
+    ///     .map(|(i, _)| i)
+    ///     .collect();
+    /// assert_eq!(taken, vec![4]);
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = (usize, Cell)> + '_ {
+        self.board.iter().copied().enumerate()
+    }
+
+    /// Returns a compact integer identifying the position: the board and the
+    /// player to move.
+    ///
+    /// Bits 0 to 17 hold the board as laid out by [`GameEngine::encode`], and
+    /// bit 18 is set when O is to move. Unlike a
+    /// [`Hash`](core::hash::Hash) output, the encoding is fixed, so keys are
+    /// equal for equal positions across runs and platforms, e.g. for external
+    /// caches and logs.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.position_key(), 0);
+    ///
+    /// game.make_move(4).unwrap(); // X in cell 4, O to move
+    /// assert_eq!(game.position_key(), 1 << 8 | 1 << 18);
+    /// ```
+    pub fn position_key(&self) -> u32 {
+        let side = match self.current_player {
+            Player::X => 0,
+            Player::O => 1,
+        };
+        self.encode() | side << 18
+    }
+
+    /// Encodes the board in 18 bits, two per cell, for compact storage.
+    ///
+    /// Cell `i` is stored in bits `2 * i` and `2 * i + 1` as `0` when empty,
+    /// `1` for X, `2` for O and `3` when blocked; the bits above 17 are zero.
+    /// [`GameEngine::decode`] reverses the encoding exactly. The player to
+    /// move isn't included, since it follows from the mark counts.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap(); // X in cell 4, O in cell 0
+    /// assert_eq!(game.encode(), 0b01 << 8 | 0b10);
+    /// ```
+    pub fn encode(&self) -> u32 {
+        self.board.iter().enumerate().fold(0, |bits, (i, cell)| {
+            let code = match cell {
+                Cell::Empty => 0,
+                Cell::X => 1,
+                Cell::O => 2,
+                Cell::Blocked => 3,
+            };
+            bits | code << (2 * i)
+        })
+    }
+
+    /// Creates a game from a board encoded with [`GameEngine::encode`], with
+    /// `current_player` to move and AI **enabled**.
+    ///
+    /// The encoding doesn't record who moved first, so unlike
+    /// [`GameEngine::from_board`] either player may have: the one that makes
+    /// it `current_player`'s turn on the board is assumed. The move history
+    /// starts empty, so `decode(game.encode(), game.current_player)` gives back
+    /// the board, the player to move and the state of `game`, including a game
+    /// from [`GameEngine::with_starting_player`].
+    ///
+    /// # Errors
+    /// - `BoardError::InvalidEncoding` if bits above 17 are set.
+    /// - `BoardError::InvalidMarkCounts` if neither player has as many marks
+    ///   as the other or exactly one more.
+    /// - `BoardError::WrongTurn` if the mark counts are possible, but it can't
+    ///   be `current_player`'s turn whoever moved first.
+    /// - `BoardError::MultipleWinners` or `BoardError::MoveAfterWin`, like
+    ///   [`GameEngine::from_board`], if the board is otherwise unreachable.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{BoardError, GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0, 8]).unwrap();
+    ///
+    /// let decoded = GameEngine::decode(game.encode(), Player::O).unwrap();
+    /// assert_eq!(decoded.get_board(), game.get_board());
+    ///
+    /// // With two X marks to one O, it is O's turn whoever went first.
+    /// assert_eq!(
+    ///     GameEngine::decode(game.encode(), Player::X).err(),
+    ///     Some(BoardError::WrongTurn(Player::X))
+    /// );
+    /// ```
+    pub fn decode(bits: u32, current_player: Player) -> Result<Self, BoardError> {
+        if bits >> 18 != 0 {
+            return Err(BoardError::InvalidEncoding(bits));
+        }
+
+        let board = core::array::from_fn(|i| match bits >> (2 * i) & 0b11 {
+            0 => Cell::Empty,
+            1 => Cell::X,
+            2 => Cell::O,
+            _ => Cell::Blocked,
+        });
+        let (x, o) = Self::count_marks(&board);
+        let Some(first) = [Player::X, Player::O]
+            .into_iter()
+            .find(|&first| Self::player_to_move(x, o, first) == Some(current_player))
+        else {
+            return Err(if x.abs_diff(o) <= 1 {
+                BoardError::WrongTurn(current_player)
+            } else {
+                BoardError::InvalidMarkCounts { x, o }
+            });
+        };
+        Self::from_board_opened_by(board, first)
+    }
+
+    /// Returns a read-only copy of the board, the current player and the
+    /// state of the game.
+    ///
+    /// The snapshot reflects the engine at the moment of the call and is not
+    /// updated by later moves.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    /// let snapshot = game.snapshot();
+    ///
+    /// game.make_move(0).unwrap();
+    /// assert_eq!(snapshot.board()[0], Cell::Empty);
+    /// assert_eq!(snapshot.current_player(), Player::O);
+    /// ```
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            board: self.board,
+            current_player: self.current_player,
+            state: self.state,
+        }
+    }
+
+    /// Turns a finished game into a [`GameRecord`] that can no longer be
+    /// played, holding its final board, result, winning line and moves.
+    ///
+    /// Returns `None` while the game is still in progress, like
+    /// [`GameEngine::result`], so check [`GameEngine::is_over`] first to keep
+    /// playing an unfinished game. Moves that were undone are not part of the
+    /// record.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 3, 1, 4, 2]).unwrap();
+    ///
+    /// let record = game.into_record().unwrap();
+    /// assert_eq!(record.state(), GameState::Win(Player::X));
+    /// assert_eq!(record.winning_line(), Some(&[0, 1, 2][..]));
+    /// assert_eq!(record.moves(), &[0, 3, 1, 4, 2]);
+    /// ```
+    pub fn into_record(self) -> Option<GameRecord> {
+        if !self.is_over() {
+            return None;
+        }
+
+        // Clear undone moves so that records of the same game compare equal.
+        let mut moves = self.history;
+        moves[self.history_len..].fill(0);
+        let mut line = [0; 3];
+        let line_len = self.winning_line().map_or(0, |cells| {
+            line[..cells.len()].copy_from_slice(cells);
+            cells.len()
+        });
+        Some(GameRecord {
+            board: self.board,
+            state: self.state,
+            moves,
+            move_len: self.history_len,
+            line,
+            line_len,
+        })
+    }
+
+    /// Returns the number of X and O marks on the board, in that order.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(8).unwrap(); // X
+    ///
+    /// assert_eq!(game.mark_counts(), (2, 1));
+    /// ```
+    pub fn mark_counts(&self) -> (usize, usize) {
+        Self::count_marks(&self.board)
+    }
+
+    /// Attempts to make a move for the current player at the given board index.
+    ///
+    /// # Parameters
+    /// - `index`: The 0-based cell index (0–8).
+    ///
+    /// # Returns
+    /// - `Ok(())` if the move was made successfully.
+    /// - `Err(MoveError)` if the move is invalid:
+    ///   - `MoveError::OutOfBounds` if `index >= 9`
+    ///   - `MoveError::GameOver` if the game is over (won, tied, resigned or
+    ///     drawn by agreement), see [`GameEngine::is_over`]
+    ///   - `MoveError::CellBlocked` if the cell is [blocked](Cell::Blocked)
+    ///   - `MoveError::CellOccupied` if the cell already has a mark
+    ///
+    /// A failed move leaves the engine unchanged, whether or not AI is enabled.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(game.make_move(0).is_ok());
+    /// assert_eq!(game.make_move(0), Err(MoveError::CellOccupied));
+    /// ```
+    pub fn make_move(&mut self, index: usize) -> Result<(), MoveError> {
+        self.check_move(index)?;
+        self.place(index);
+
+        // A new move discards any undone moves.
+        self.recorded_len = self.history_len;
+        Ok(())
+    }
+
+    /// Checks a move for the current player at `index`, returning the error
+    /// [`GameEngine::make_move`] would.
+    fn check_move(&self, index: usize) -> Result<(), MoveError> {
+        // First, check if the index is within the valid range of the board.
+        if index >= 9 {
+            return Err(MoveError::OutOfBounds);
+        }
+
+        // No moves can follow a win or a tie.
+        self.assert_in_progress()?;
+
+        // Then, check if the cell can be played at all, and is still free.
+        if self.board[index] == Cell::Blocked {
+            return Err(MoveError::CellBlocked);
+        }
+        if self.board[index] != Cell::Empty {
+            return Err(MoveError::CellOccupied);
+        }
+        Ok(())
+    }
+
+    /// Makes a move like [`GameEngine::make_move`], returning a token that
+    /// [`GameEngine::undo_tok`] uses to take it back.
+    ///
+    /// Together these are the do/undo pair of a game-tree search: making and
+    /// undoing a move costs O(1), with no copy of the engine. Tokens must be
+    /// undone in LIFO order, the most recent move first.
+    ///
+    /// # Returns
+    /// - `Ok(MoveToken)` if the move was made successfully.
+    /// - `Err(MoveError)` for the same invalid moves as [`GameEngine::make_move`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// let before = game.snapshot();
+    ///
+    /// let first = game.make_move_tok(4).unwrap();
+    /// let second = game.make_move_tok(0).unwrap();
+    /// game.undo_tok(second);
+    /// game.undo_tok(first);
+    ///
+    /// assert_eq!(game.snapshot(), before);
+    /// ```
+    pub fn make_move_tok(&mut self, index: usize) -> Result<MoveToken, MoveError> {
+        let state = self.state;
+        let recorded_len = self.recorded_len;
+        let replaced = self.history.get(self.history_len).copied().unwrap_or(0);
+        self.make_move(index)?;
+        Ok(MoveToken {
+            index,
+            state,
+            replaced,
+            recorded_len,
+        })
+    }
+
+    /// Takes back the move that `tok` was returned for, restoring the engine,
+    /// including any moves available to redo, to how it was before the move.
+    ///
+    /// # Panics
+    /// Panics if `tok` isn't for the most recent move; tokens must be undone
+    /// in LIFO order.
+    pub fn undo_tok(&mut self, tok: MoveToken) {
+        assert!(
+            self.history_len > 0 && self.history[self.history_len - 1] == tok.index,
+            "move tokens must be undone in LIFO order"
+        );
+
+        self.history_len -= 1;
+        self.history[self.history_len] = tok.replaced;
+        self.recorded_len = tok.recorded_len;
+        self.board[tok.index] = Cell::Empty;
+        self.state = tok.state;
+        self.current_player = self.current_player.opponent();
+    }
+
+    /// Checks that the game is still in progress, so that moves can be made.
+    ///
+    /// # Returns
+    /// - `Ok(())` while the game is in progress.
+    /// - `Err(MoveError::GameOver)` once the game is over (won, tied, resigned
+    ///   or drawn by agreement), see [`GameEngine::is_over`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::with_ai(false);
+    /// assert_eq!(game.assert_in_progress(), Ok(()));
+    ///
+    /// game.simulate_moves(&[0, 3, 1, 4, 2]).unwrap(); // X wins
+    /// assert_eq!(game.assert_in_progress(), Err(MoveError::GameOver));
+    /// assert_eq!(game.make_move(5), Err(MoveError::GameOver));
+    /// ```
+    pub fn assert_in_progress(&self) -> Result<(), MoveError> {
+        if self.is_over() {
+            return Err(MoveError::GameOver);
+        }
+        Ok(())
+    }
+
+    /// Ends the game with `player` resigning, giving the win to their opponent.
+    ///
+    /// The state becomes [`GameState::Resigned`], so no more moves can be made,
+    /// and moves undone earlier can no longer be redone. Undoing a move takes
+    /// back the resignation along with it. Has no effect once the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, MoveError, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    /// game.resign(Player::O);
+    ///
+    /// assert_eq!(game.check_state(), GameState::Resigned(Player::O));
+    /// assert_eq!(game.check_state().winner(), Some(Player::X));
+    /// assert_eq!(game.make_move(0), Err(MoveError::GameOver));
+    /// ```
+    pub fn resign(&mut self, player: Player) {
+        self.end(GameState::Resigned(player));
+    }
+
+    /// Ends the game in a draw agreed by both players, without filling the board.
+    ///
+    /// The state becomes [`GameState::DrawAgreed`], with the same effects as
+    /// [`GameEngine::resign`]. Has no effect once the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, MoveError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    /// game.agree_draw();
+    ///
+    /// assert!(game.is_over());
+    /// assert_eq!(game.check_state(), GameState::DrawAgreed);
+    /// assert_eq!(game.make_move(8), Err(MoveError::GameOver));
+    /// ```
+    pub fn agree_draw(&mut self) {
+        self.end(GameState::DrawAgreed);
+    }
+
+    /// Ends a game in progress with `state`, discarding any undone moves.
+    fn end(&mut self, state: GameState) {
+        if self.is_over() {
+            return;
+        }
+        self.state = state;
+        self.recorded_len = self.history_len;
+    }
+
+    /// Plays each move in `moves` in order, stopping at the first invalid one.
+    ///
+    /// This is handy for replaying a saved game.
+    ///
+    /// # Returns
+    /// - `Ok(())` if every move was made; the engine reflects all of them.
+    /// - `Err((position, error))` for the first invalid move, where `position`
+    ///   is its position within `moves`. Moves before it remain applied.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, MoveError, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1, 5, 2]).unwrap();
+    /// assert_eq!(game.check_state(), GameState::Win(Player::X));
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(
+    ///     game.simulate_moves(&[0, 4, 4]),
+    ///     Err((2, MoveError::CellOccupied))
+    /// );
+    /// ```
+    pub fn simulate_moves(&mut self, moves: &[usize]) -> Result<(), (usize, MoveError)> {
+        for (position, &index) in moves.iter().enumerate() {
+            self.make_move(index).map_err(|error| (position, error))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the position as a compact JSON object, for logging and simple
+    /// interop without extra dependencies.
+    ///
+    /// The object always has the same keys in the same order, with no
+    /// whitespace:
+    /// - `"board"`: the 9 cells in index order, each a one-character string
+    ///   as the [`Cell`] is displayed: `"X"`, `"O"`, `"."` when empty, or
+    ///   `"#"` when [blocked](Cell::Blocked).
+    /// - `"turn"`: the player to move, `"X"` or `"O"`, or `null` once the game
+    ///   is over.
+    /// - `"state"`: `"InProgress"`, `"Tie"`, `"Win"`, `"Resigned"` or `"DrawAgreed"`.
+    /// - `"winner"`: the winning player, or `null` unless `"state"` is `"Win"`
+    ///   or `"Resigned"`, where it is the opponent of the player who resigned.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    /// assert_eq!(
+    ///     game.to_json(),
+    ///     r#"{"board":["O",".",".",".","X",".",".",".","."],"turn":"X","state":"InProgress","winner":null}"#
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_json(&self) -> String {
+        let quoted = |player: Player| format!("\"{}\"", player);
+        let cells: Vec<String> = self
+            .board
+            .iter()
+            .map(|cell| format!("\"{}\"", cell))
+            .collect();
+        let turn = self.turn().map_or(String::from("null"), quoted);
+        let (state, winner) = match self.state {
+            GameState::Win(player) => ("Win", quoted(player)),
+            GameState::Tie => ("Tie", String::from("null")),
+            GameState::InProgress => ("InProgress", String::from("null")),
+            GameState::Resigned(player) => ("Resigned", quoted(player.opponent())),
+            GameState::DrawAgreed => ("DrawAgreed", String::from("null")),
+        };
+        format!(
+            "{{\"board\":[{}],\"turn\":{},\"state\":\"{}\",\"winner\":{}}}",
+            cells.join(","),
+            turn,
+            state,
+            winner
+        )
+    }
+
+    /// Writes the moves played so far as a text transcript.
+    ///
+    /// Each move is written as the player's mark followed by the cell's
+    /// coordinates: a column letter `a`–`c` from left to right and a row
+    /// number `1`–`3` from top to bottom, so `a1` is cell 0, `b2` the center
+    /// and `c3` cell 8. Moves are separated by single spaces, e.g.
+    /// `"Xb2 Oa1 Xc3"`. [`GameEngine::from_notation`] reads it back.
+    ///
+    /// Only the moves in the [history](GameEngine::get_history) are written, so
+    /// marks loaded with [`GameEngine::from_board`] are not included.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0, 8]).unwrap();
+    /// assert_eq!(game.to_notation(), "Xb2 Oa1 Xc3");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_notation(&self) -> String {
+        let moves: Vec<String> = self
+            .history_moves()
+            .map(|(player, index)| {
+                let column = (b'a' + (index % 3) as u8) as char;
+                let row = index / 3 + 1;
+                format!("{}{}{}", player, column, row)
+            })
+            .collect();
+        moves.join(" ")
+    }
+
+    /// Creates a game by replaying a transcript written by [`GameEngine::to_notation`].
+    ///
+    /// The player of the first move starts the game, and AI is **enabled**.
+    /// Surrounding and repeated whitespace between moves is ignored, and an
+    /// empty transcript gives a new game.
+    ///
+    /// # Errors
+    /// Each error holds the position of the offending move in the transcript:
+    /// - `NotationError::InvalidMove` if a move isn't a mark and coordinates.
+    /// - `NotationError::WrongPlayer` if a move is by the player not on turn.
+    /// - `NotationError::IllegalMove` if the move can't be made.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError, NotationError, Player};
+    ///
+    /// let game = GameEngine::from_notation("Xb2 Oa1 Xc3").unwrap();
+    /// assert_eq!(game.get_history(), &[4, 0, 8]);
+    /// assert_eq!(game.current_player, Player::O);
+    ///
+    /// assert_eq!(
+    ///     GameEngine::from_notation("Xb2 Ob2").err(),
+    ///     Some(NotationError::IllegalMove(1, MoveError::CellOccupied))
+    /// );
+    /// ```
+    pub fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let mut game = Self::new();
+        game.play_notation(notation)?;
+        Ok(game)
+    }
+
+    /// Replaces the game in progress with one replayed from a transcript
+    /// written by [`GameEngine::to_notation`], keeping the engine's settings.
+    ///
+    /// The board is cleared, apart from [blocked](Cell::Blocked) cells, and
+    /// the history starts over. The player of the first move starts the game,
+    /// or X for an empty transcript. Loading doesn't call the
+    /// [move callback](GameEngine::set_on_move).
+    ///
+    /// # Errors
+    /// Returns the same errors as [`GameEngine::from_notation`], and leaves
+    /// the engine exactly as it was before the call.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError, NotationError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap();
+    ///
+    /// game.try_load_notation("Xb2 Oa1").unwrap();
+    /// assert_eq!(game.get_history(), &[4, 0]);
+    ///
+    /// assert_eq!(
+    ///     game.try_load_notation("Xa1 Oa1"),
+    ///     Err(NotationError::IllegalMove(1, MoveError::CellOccupied))
+    /// );
+    /// assert_eq!(game.get_history(), &[4, 0]);
+    /// ```
+    pub fn try_load_notation(&mut self, notation: &str) -> Result<(), NotationError> {
+        let mut game = self.clone();
+        for cell in game.board.iter_mut().filter(|cell| **cell != Cell::Blocked) {
+            *cell = Cell::Empty;
+        }
+        game.state = game.check_board_state(game.board);
+        game.current_player = Player::X;
+        game.history_len = 0;
+        game.recorded_len = 0;
+        game.play_notation(notation)?;
+
+        self.board = game.board;
+        self.state = game.state;
+        self.current_player = game.current_player;
+        self.history = game.history;
+        self.history_len = game.history_len;
+        self.recorded_len = game.recorded_len;
+        Ok(())
+    }
+
+    /// Plays the moves of a transcript, with the player of the first move
+    /// taking the turn.
+    fn play_notation(&mut self, notation: &str) -> Result<(), NotationError> {
+        for (position, token) in notation.split_whitespace().enumerate() {
+            let (player, index) =
+                Self::parse_notation_move(token).ok_or(NotationError::InvalidMove(position))?;
+            if position == 0 {
+                self.current_player = player;
+            } else if player != self.current_player {
+                return Err(NotationError::WrongPlayer(position));
+            }
+            self.make_move(index)
+                .map_err(|error| NotationError::IllegalMove(position, error))?;
+        }
+        Ok(())
+    }
+
+    /// Parses a single move of the notation, such as `Xb2`.
+    fn parse_notation_move(token: &str) -> Option<(Player, usize)> {
+        let &[mark, column, row] = token.as_bytes() else {
+            return None;
+        };
+        let player = match mark {
+            b'X' => Player::X,
+            b'O' => Player::O,
+            _ => return None,
+        };
+        Some((player, Self::square_index(column, row)?))
+    }
+
+    /// Returns the index of the cell at chess-like coordinates, with column
+    /// `a`–`c` and row `1`–`3`, or `None` if they are off the board.
+    fn square_index(column: u8, row: u8) -> Option<usize> {
+        if !(b'a'..=b'c').contains(&column) || !(b'1'..=b'3').contains(&row) {
+            return None;
+        }
+        Some((row - b'1') as usize * 3 + (column - b'a') as usize)
+    }
+
+    /// Parses a move typed by a player into a cell index.
+    ///
+    /// Surrounding whitespace is ignored. The accepted formats are:
+    /// - A plain index `0`–`8`, e.g. `"4"`.
+    /// - A 0-based `row,col` pair, e.g. `"1,2"` for cell 5, as in
+    ///   [`GameEngine::get_grid`]. Spaces around the comma are allowed.
+    /// - Chess-like coordinates `a1`–`c3`, as in [`GameEngine::to_notation`]:
+    ///   a column letter from the left and a row number from the top, e.g.
+    ///   `"b2"` for the center. The letter may be upper case.
+    ///
+    /// # Errors
+    /// - `ParseMoveError::OutOfBounds` if the input has one of the formats
+    ///   but names a cell off the board, e.g. `"9"`, `"3,0"` or `"d1"`.
+    /// - `ParseMoveError::InvalidFormat` if the input has none of the formats.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, ParseMoveError};
+    ///
+    /// assert_eq!(GameEngine::parse_move("4"), Ok(4));
+    /// assert_eq!(GameEngine::parse_move("1,2"), Ok(5));
+    /// assert_eq!(GameEngine::parse_move("b2"), Ok(4));
+    /// assert_eq!(GameEngine::parse_move("9"), Err(ParseMoveError::OutOfBounds));
+    /// assert_eq!(GameEngine::parse_move("top"), Err(ParseMoveError::InvalidFormat));
+    /// ```
+    pub fn parse_move(input: &str) -> Result<usize, ParseMoveError> {
+        let input = input.trim();
+        let parse = |number: &str| {
+            number
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| ParseMoveError::InvalidFormat)
+        };
+
+        if let Some((row, column)) = input.split_once(',') {
+            let (row, column) = (parse(row)?, parse(column)?);
+            if row >= 3 || column >= 3 {
+                return Err(ParseMoveError::OutOfBounds);
+            }
+            return Ok(row * 3 + column);
+        }
+
+        if let &[column, row] = input.as_bytes()
+            && column.is_ascii_alphabetic()
+            && row.is_ascii_digit()
+        {
+            return Self::square_index(column.to_ascii_lowercase(), row)
+                .ok_or(ParseMoveError::OutOfBounds);
+        }
+
+        let index = parse(input)?;
+        if index >= 9 {
+            return Err(ParseMoveError::OutOfBounds);
+        }
+        Ok(index)
+    }
+
+    /// Returns `true` if the current player may play at `index`.
+    ///
+    /// A move is valid when `index` is on the board (`0..9`), the cell is
+    /// empty, and the game is still in progress. This lets UIs check a move
+    /// without attempting it.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap();
+    ///
+    /// assert!(game.is_valid_move(1));
+    /// assert!(!game.is_valid_move(0)); // Occupied
+    /// assert!(!game.is_valid_move(9)); // Out of bounds
+    /// ```
+    pub fn is_valid_move(&self, index: usize) -> bool {
+        index < 9 && self.board[index] == Cell::Empty && !self.is_over()
+    }
+
+    /// Returns whether a move at each cell is legal, as a consistent snapshot
+    /// for rendering which cells can be clicked.
+    ///
+    /// Each entry is `Ok(())` if [`GameEngine::make_move`] would accept a move
+    /// at that index, or the error it would return. Once the game is over,
+    /// every entry is `Err(MoveError::GameOver)`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    ///
+    /// let legality = game.move_legality();
+    /// assert_eq!(legality[4], Err(MoveError::CellOccupied));
+    /// assert_eq!(legality[0], Ok(()));
+    /// ```
+    pub fn move_legality(&self) -> [Result<(), MoveError>; 9] {
+        core::array::from_fn(|index| self.check_move(index))
+    }
+
+    /// Returns an iterator over every legal move paired with a copy of the
+    /// engine after that move, in index order.
+    ///
+    /// This branches the position for tree searches outside the engine. Each
+    /// successor is a clone, so it doesn't keep the
+    /// [move callback](GameEngine::set_on_move). Yields nothing once the game
+    /// is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    ///
+    /// let successors: Vec<_> = game.successors().collect();
+    /// assert_eq!(successors.len(), 8);
+    ///
+    /// let (index, next) = &successors[0];
+    /// assert_eq!(*index, 0);
+    /// assert_eq!(next.get_board()[0], Cell::O);
+    /// ```
+    pub fn successors(&self) -> impl Iterator<Item = (usize, GameEngine)> + '_ {
+        (0..9)
+            .filter(|&index| self.is_valid_move(index))
+            .map(|index| {
+                let mut game = self.clone();
+                game.make_move(index).expect("only valid moves are played");
+                (index, game)
+            })
+    }
+
+    /// Undoes the most recent move, returning the index it was played at.
+    ///
+    /// The undone move can be reapplied with [`GameEngine::redo_move`] until a
+    /// new move is made.
+    ///
+    /// # Returns
+    /// - `Ok(index)` with the cell that was cleared.
+    /// - `Err(MoveError::NothingToUndo)` if no moves have been played.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Cell, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    ///
+    /// assert_eq!(game.undo_move(), Ok(4));
+    /// assert_eq!(game.get_board()[4], Cell::Empty);
+    /// assert_eq!(game.current_player, Player::X);
+    /// ```
+    pub fn undo_move(&mut self) -> Result<usize, MoveError> {
+        if self.history_len == 0 {
+            return Err(MoveError::NothingToUndo);
+        }
+
+        self.history_len -= 1;
+        let index = self.history[self.history_len];
+        self.board[index] = Cell::Empty;
+        self.state = self.check_board_state(self.board);
+
+        // The player who made the undone move is on turn again.
+        self.current_player = self.current_player.opponent();
+        Ok(index)
+    }
+
+    /// Reapplies the most recently undone move, returning the index it was played at.
+    ///
+    /// Redo follows the usual editor semantics: making a new move with
+    /// [`GameEngine::make_move`] clears the moves available to redo.
+    ///
+    /// # Returns
+    /// - `Ok(index)` with the cell that was played again.
+    /// - `Err(MoveError::NothingToRedo)` if there is no undone move.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(4).unwrap();
+    /// game.undo_move().unwrap();
+    ///
+    /// assert_eq!(game.redo_move(), Ok(4));
+    /// assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+    /// ```
+    pub fn redo_move(&mut self) -> Result<usize, MoveError> {
+        if self.history_len == self.recorded_len {
+            return Err(MoveError::NothingToRedo);
+        }
+
+        let index = self.history[self.history_len];
+        self.place(index);
+        Ok(index)
+    }
+
+    /// Moves through the history to the position after exactly `move_number`
+    /// moves have been played.
+    ///
+    /// Moves after `move_number` are undone and can still be redone, so this can
+    /// also jump forward again to any move that was undone. `move_number = 0`
+    /// returns to the start of the history.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the position is reached.
+    /// - `Err(MoveError::NothingToRedo)` if fewer than `move_number` moves have
+    ///   been recorded; the position is left unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0, 8, 2]).unwrap();
+    ///
+    /// game.undo_to(1).unwrap();
+    /// assert_eq!(game.get_history(), &[4]);
+    ///
+    /// game.undo_to(3).unwrap();
+    /// assert_eq!(game.get_history(), &[4, 0, 8]);
+    ///
+    /// assert_eq!(game.undo_to(5), Err(MoveError::NothingToRedo));
+    /// ```
+    pub fn undo_to(&mut self, move_number: usize) -> Result<(), MoveError> {
+        if move_number > self.recorded_len {
+            return Err(MoveError::NothingToRedo);
+        }
+
+        while self.history_len > move_number {
+            self.undo_move()?;
+        }
+        while self.history_len < move_number {
+            self.redo_move()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the most recently played move, or `None` if no
+    /// moves have been made.
+    ///
+    /// Undone moves are not counted, so this follows [`GameEngine::undo_move`]
+    /// and [`GameEngine::redo_move`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.last_move(), None);
+    ///
+    /// game.make_move(4).unwrap();
+    /// assert_eq!(game.last_move(), Some(4));
+    /// ```
+    pub fn last_move(&self) -> Option<usize> {
+        self.history[..self.history_len].last().copied()
+    }
+
+    /// Returns the moves played so far, in order, as cell indices.
+    ///
+    /// Undone moves are not included. Marks that were already on the board
+    /// when the game was created with [`GameEngine::from_board`] are not part
+    /// of the history either.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0, 8]).unwrap();
+    /// game.undo_move().unwrap();
+    ///
+    /// assert_eq!(game.get_history(), &[4, 0]);
+    /// ```
+    pub fn get_history(&self) -> &[usize] {
+        &self.history[..self.history_len]
+    }
+
+    /// Returns `true` if both engines played the same moves in the same order
+    /// from the same starting position.
+    ///
+    /// Two games can reach the same board through different move orders; this
+    /// tells them apart, e.g. to deduplicate saved games. Undone moves are
+    /// ignored, like in [`GameEngine::get_history`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut a = GameEngine::new();
+    /// a.simulate_moves(&[4, 0, 8]).unwrap();
+    /// let mut b = GameEngine::new();
+    /// b.simulate_moves(&[8, 0, 4]).unwrap();
+    ///
+    /// assert_eq!(a.get_board(), b.get_board());
+    /// assert!(!a.same_line(&b));
+    /// ```
+    pub fn same_line(&self, other: &GameEngine) -> bool {
+        // Equal histories from different starting boards end on different boards.
+        self.get_history() == other.get_history() && self.board == other.board
+    }
+
+    /// Returns the cells whose contents differ between this board and
+    /// `other`'s, as `(index, old, new)` in index order, where `old` is the
+    /// cell here and `new` the cell in `other`.
+    ///
+    /// This is the minimal update that turns this board into the other, e.g.
+    /// for syncing boards over a network. Diffing the other way swaps `old`
+    /// and `new`. Only boards are compared, not histories or turns.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine};
+    ///
+    /// let before = GameEngine::new();
+    /// let mut after = before.clone();
+    /// after.make_move(4).unwrap();
+    ///
+    /// assert_eq!(before.diff(&after), vec![(4, Cell::Empty, Cell::X)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn diff(&self, other: &GameEngine) -> Vec<(usize, Cell, Cell)> {
+        self.cells()
+            .zip(other.board)
+            .filter(|&((_, old), new)| old != new)
+            .map(|((index, old), new)| (index, old, new))
+            .collect()
+    }
+
+    /// Returns every move in the history paired with the player who made it.
+    ///
+    /// Unlike [`GameEngine::get_history`], this is unambiguous about who moved
+    /// when, including in games that O started.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::with_starting_player(Player::O, false);
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    ///
+    /// assert_eq!(game.export_moves(), vec![(Player::O, 4), (Player::X, 0)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn export_moves(&self) -> Vec<(Player, usize)> {
+        self.history_moves().collect()
+    }
+
+    /// Creates a game by replaying moves paired with the player who made them,
+    /// as returned by [`GameEngine::export_moves`].
+    ///
+    /// The player of the first move starts the game. Every move must be made
+    /// by the player on turn and be legal in the position reached.
+    ///
+    /// # Errors
+    /// Returns `(position, error)` for the first move that fails, where
+    /// `position` is its position within `moves`:
+    /// - `MoveError::OutOfTurn` if the move is by the player not on turn.
+    /// - Any error of [`GameEngine::make_move`] if the move is illegal.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, MoveError, Player};
+    ///
+    /// let moves = [(Player::O, 4), (Player::X, 0)];
+    /// let game = GameEngine::import_moves(&moves, false).unwrap();
+    /// assert_eq!(game.export_moves(), moves);
+    ///
+    /// let moves = [(Player::X, 4), (Player::X, 0)];
+    /// assert_eq!(
+    ///     GameEngine::import_moves(&moves, false).err(),
+    ///     Some((1, MoveError::OutOfTurn))
+    /// );
+    /// ```
+    pub fn import_moves(
+        moves: &[(Player, usize)],
+        ai_enabled: bool,
+    ) -> Result<Self, (usize, MoveError)> {
+        let starting = moves.first().map_or(Player::X, |&(player, _)| player);
+        let mut game = Self::with_starting_player(starting, ai_enabled);
+        for (position, &(player, index)) in moves.iter().enumerate() {
+            if player != game.current_player {
+                return Err((position, MoveError::OutOfTurn));
+            }
+            game.make_move(index).map_err(|error| (position, error))?;
+        }
+        Ok(game)
+    }
+
+    /// Iterates over the moves in the history with the player who made each.
+    #[cfg(feature = "alloc")]
+    fn history_moves(&self) -> impl Iterator<Item = (Player, usize)> + '_ {
+        // The players alternate, so after an even number of moves the player
+        // to move is the one who moved first.
+        let first = if self.history_len.is_multiple_of(2) {
+            self.current_player
+        } else {
+            self.current_player.opponent()
+        };
+        self.get_history()
+            .iter()
+            .enumerate()
+            .map(move |(i, &index)| {
+                let player = if i.is_multiple_of(2) {
+                    first
+                } else {
+                    first.opponent()
+                };
+                (player, index)
+            })
+    }
+
+    /// Returns an iterator over the board after each move in the history.
+    ///
+    /// The first board is the one after move 1 and the last is the current
+    /// board, so a game with no moves yields nothing. The starting position is
+    /// not included; it is empty unless the game was created with
+    /// [`GameEngine::from_board`]. The engine itself is not modified.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0]).unwrap();
+    ///
+    /// let frames: Vec<[Cell; 9]> = game.replay().collect();
+    /// assert_eq!(frames.len(), 2);
+    /// assert_eq!(frames[0][4], Cell::X);
+    /// assert_eq!(frames[0][0], Cell::Empty);
+    /// assert_eq!(&frames[1], game.get_board());
+    /// ```
+    pub fn replay(&self) -> impl Iterator<Item = [Cell; 9]> + '_ {
+        // Clear the moves in the history to recover the starting position.
+        let mut start = self.board;
+        for &index in self.get_history() {
+            start[index] = Cell::Empty;
+        }
+
+        self.get_history().iter().scan(start, |board, &index| {
+            board[index] = self.board[index];
+            Some(*board)
+        })
+    }
+
+    /// Returns the number of moves made so far, i.e. the number of marks on the board.
+    ///
+    /// Unlike `get_history().len()`, this also counts the marks of a board
+    /// loaded with [`GameEngine::from_board`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 0, 8]).unwrap();
+    ///
+    /// assert_eq!(game.move_count(), 3);
+    /// ```
+    pub fn move_count(&self) -> usize {
+        let (x, o) = self.mark_counts();
+        x + o
+    }
+
+    /// Returns the stage the game has reached, judged by
+    /// [`move_count`](GameEngine::move_count):
+    ///
+    /// | Marks on the board | Phase                  |
+    /// |--------------------|------------------------|
+    /// | 0–2                | [`GamePhase::Opening`] |
+    /// | 3–6                | [`GamePhase::Midgame`] |
+    /// | 7–9                | [`GamePhase::Endgame`] |
+    ///
+    /// The thresholds are those of [`GamePhase::from_move_count`].
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GamePhase};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.phase(), GamePhase::Opening);
+    ///
+    /// game.simulate_moves(&[4, 0, 8]).unwrap();
+    /// assert_eq!(game.phase(), GamePhase::Midgame);
+    /// ```
+    pub fn phase(&self) -> GamePhase {
+        GamePhase::from_move_count(self.move_count())
+    }
+
+    /// Returns `true` if the board has no marks, i.e. no moves have been played.
+    /// Blocked cells don't count as marks.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(game.is_empty());
+    ///
+    /// game.make_move(4).unwrap();
+    /// assert!(!game.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.move_count() == 0
+    }
+
+    /// Returns `true` if every cell of the board has a mark or is blocked.
+    ///
+    /// A full board always ends the game, but a game can end before the board
+    /// is full.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 1, 2, 4, 3, 5, 7, 6, 8]).unwrap();
+    /// assert!(game.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        !self.board.contains(&Cell::Empty)
+    }
+
+    /// Sets a callback invoked whenever a move is applied.
+    ///
+    /// The callback receives the player who moved and the index they played,
+    /// after the board has been updated. It fires for [`GameEngine::make_move`]
+    /// and [`GameEngine::redo_move`], including moves applied by helpers such as
+    /// [`GameEngine::play_out`], but not for [`GameEngine::undo_move`].
+    /// Setting a new callback replaces the previous one.
+    ///
+    /// Callbacks are not cloned: a clone of the engine starts without one.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::mpsc;
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut game = GameEngine::new();
+    /// game.set_on_move(Box::new(move |player, index| {
+    ///     sender.send((player, index)).unwrap();
+    /// }));
+    ///
+    /// game.make_move(4).unwrap();
+    /// assert_eq!(receiver.try_recv(), Ok((Player::X, 4)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn set_on_move(&mut self, f: Box<dyn FnMut(Player, usize) + Send>) {
+        self.on_move = OnMove(Some(f));
+    }
+
+    /// Blocks every cell in the mask `cells`, which must be empty, and updates
+    /// the state of the game to match.
+    pub(crate) fn block_cells(&mut self, cells: u16) {
+        for index in bitboard::indices(cells) {
+            self.board[index] = Cell::Blocked;
+        }
+        self.state = self.check_board_state(self.board);
+    }
+
+    /// Places the current player's mark at `index`, records it in the history
+    /// and passes the turn. The move must already be validated.
+    fn place(&mut self, index: usize) {
+        let player = self.current_player;
+
+        // Place the current player's mark on the board.
+        match player {
+            Player::X => self.board[index] = Cell::X,
+            Player::O => self.board[index] = Cell::O,
+        }
+
+        self.history[self.history_len] = index;
+        self.history_len += 1;
+
+        // Only the lines through the new mark can have been completed.
+        let completed = self.lines.masks().iter().any(|&line| {
+            line & (1 << index) != 0
+                && bitboard::indices(line).all(|i| self.board[i] == self.board[index])
+        });
+        if completed {
+            self.state = GameState::Win(player);
+        } else if !self.board.contains(&Cell::Empty) {
+            self.state = GameState::Tie;
+        }
+
+        // Switch to the other player for the next turn.
+        self.current_player = player.opponent();
+
+        #[cfg(feature = "alloc")]
+        if let Some(on_move) = &mut self.on_move.0 {
+            on_move(player, index);
+        }
+    }
+
+    /// Returns the current state of the game.
+    ///
+    /// Possible values:
+    /// - `GameState::InProgress`
+    /// - `GameState::Tie`
+    /// - `GameState::Won(Player::X)`
+    /// - `GameState::Won(Player::O)`
+    /// - `GameState::Resigned(player)`
+    /// - `GameState::DrawAgreed`
+    ///
+    /// The state is updated as moves are made and undone, so this is cheap
+    /// to call repeatedly.
+    pub fn check_state(&self) -> GameState {
+        self.state
+    }
+
+    /// Returns `true` if the game is finished (win, draw, resignation or agreed draw).
+    pub fn is_over(&self) -> bool {
+        !matches!(self.check_state(), GameState::InProgress)
+    }
+
+    /// Returns the player whose turn it is, or `None` once the game is over.
+    ///
+    /// Prefer this over reading `current_player` directly when displaying
+    /// whose turn it is, as `current_player` still names a player after the
+    /// game has ended.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.turn(), Some(Player::X));
+    ///
+    /// for m in [0, 3, 1, 4, 2] {
+    ///     game.make_move(m).unwrap();
+    /// }
+    /// assert_eq!(game.turn(), None); // X won
+    /// ```
+    pub fn turn(&self) -> Option<Player> {
+        if self.is_over() {
+            None
+        } else {
+            Some(self.current_player)
+        }
+    }
+
+    /// Returns the opponent of the current player.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.opponent(), Player::O);
+    ///
+    /// game.make_move(4).unwrap();
+    /// assert_eq!(game.opponent(), Player::X);
+    /// ```
+    pub fn opponent(&self) -> Player {
+        self.current_player.opponent()
+    }
+
+    /// Returns the cell indices of the line that won the game, or `None` if
+    /// nobody has won.
+    ///
+    /// The indices are listed in order along the line. If the winning move
+    /// completed several lines at once, the first in row, column, diagonal
+    /// order is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 3, 4, 5, 8]).unwrap();
+    ///
+    /// assert_eq!(game.winning_line(), Some(&[0, 4, 8][..]));
+    /// ```
+    pub fn winning_line(&self) -> Option<&[usize]> {
+        let GameState::Win(player) = self.check_state() else {
+            return None;
+        };
+        let marks = Bitboard::from_cells(&self.board).marks(player);
+        let i = self
+            .lines
+            .masks()
+            .iter()
+            .position(|&mask| mask & !marks == 0)?;
+        Some(self.lines.cells(i))
+    }
+
+    /// Returns a summary of the finished game, or `None` while it is still in progress.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 3, 1, 4]).unwrap();
+    /// assert_eq!(game.result(), None);
+    ///
+    /// game.make_move(2).unwrap();
+    /// let result = game.result().unwrap();
+    /// assert_eq!(result.state, GameState::Win(Player::X));
+    /// assert_eq!(result.winning_line, Some(vec![0, 1, 2]));
+    /// assert_eq!(result.moves, vec![0, 3, 1, 4, 2]);
+    /// assert_eq!(result.move_count, 5);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn result(&self) -> Option<GameResult> {
+        let state = self.check_state();
+        if state == GameState::InProgress {
+            return None;
+        }
+        Some(GameResult {
+            state,
+            winning_line: self.winning_line().map(<[usize]>::to_vec),
+            moves: self.get_history().to_vec(),
+            move_count: self.move_count(),
+        })
+    }
+
+    /// Returns a move that wins the game on the spot for the current player,
+    /// the lowest index if there are several, or `None` if there is none.
+    ///
+    /// This only looks one move ahead, so it is much cheaper than a full
+    /// search, e.g. for quick hints. Returns `None` if the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    /// assert_eq!(game.immediate_win(), None); // O can only block
+    ///
+    /// game.make_move(8).unwrap(); // O doesn't
+    /// assert_eq!(game.immediate_win(), Some(2));
+    /// ```
+    pub fn immediate_win(&self) -> Option<usize> {
+        let player = self.turn()?;
+        let board = Bitboard::from_cells(&self.board);
+        bitboard::indices(board.winning_cells(player, &self.lines)).next()
+    }
+
+    /// Returns the cell the current player must take to stop the opponent
+    /// winning on their next turn, or `None` if the opponent has no immediate
+    /// threat or the game is over.
+    ///
+    /// If the opponent threatens two or more cells at once, the lowest index
+    /// is returned: blocking it is as good as any, but can't save the game
+    /// unless the current player has a win of their own, which
+    /// [`GameEngine::in_losing_fork`] detects. The block is returned even when
+    /// [`GameEngine::immediate_win`] finds a win, which a hint should prefer.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4]).unwrap();
+    /// assert_eq!(game.required_block(), None);
+    ///
+    /// game.simulate_moves(&[1]).unwrap(); // X threatens 2
+    /// assert_eq!(game.required_block(), Some(2));
+    /// ```
+    pub fn required_block(&self) -> Option<usize> {
+        let player = self.turn()?;
+        let board = Bitboard::from_cells(&self.board);
+        bitboard::indices(board.winning_cells(player.opponent(), &self.lines)).next()
+    }
+
+    /// Returns every empty cell where placing `player`'s mark would immediately
+    /// complete a line.
+    ///
+    /// This works regardless of whose turn it is, so it can be used to show the
+    /// threats of both players. Returns an empty `Vec` if the game is already over.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// assert_eq!(game.winning_moves_for(Player::X), vec![2]);
+    /// assert!(game.winning_moves_for(Player::O).is_empty());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn winning_moves_for(&self, player: Player) -> Vec<usize> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let board = Bitboard::from_cells(&self.board);
+        bitboard::indices(board.winning_cells(player, &self.lines)).collect()
+    }
+
+    /// Returns the threats of both players at once, as `(x_threats, o_threats)`.
+    ///
+    /// Each list holds the cells that would immediately win for that player,
+    /// as [`GameEngine::winning_moves_for`] returns them, regardless of whose
+    /// turn it is. Both are empty once the game is over.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.all_threats(), (vec![], vec![]));
+    ///
+    /// game.simulate_moves(&[0, 3, 1]).unwrap();
+    /// assert_eq!(game.all_threats(), (vec![2], vec![]));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn all_threats(&self) -> (Vec<usize>, Vec<usize>) {
+        (
+            self.winning_moves_for(Player::X),
+            self.winning_moves_for(Player::O),
+        )
+    }
+
+    /// Returns `true` if the current player faces a fork they can't stop: the
+    /// opponent threatens to complete two or more lines, and the current player
+    /// has no immediate win of their own to play instead.
+    ///
+    /// Only one threat can be blocked per move, so the opponent wins next turn
+    /// whatever the current player does. Returns `false` once the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 1, 4, 8]).unwrap();
+    /// assert!(!game.in_losing_fork());
+    ///
+    /// game.make_move(6).unwrap(); // X now threatens both 2 and 3
+    /// assert!(game.in_losing_fork());
+    /// ```
+    pub fn in_losing_fork(&self) -> bool {
+        let Some(player) = self.turn() else {
+            return false;
+        };
+
+        let board = Bitboard::from_cells(&self.board);
+        board.winning_cells(player, &self.lines) == 0
+            && board
+                .winning_cells(player.opponent(), &self.lines)
+                .count_ones()
+                >= 2
+    }
+
+    /// Returns every legal move that doesn't blunder the game away at once, in
+    /// index order.
+    ///
+    /// A move is safe unless it lets the opponent win on their next turn, or
+    /// lets them create two threats at once that the current player can
+    /// neither block both of nor answer with a win of their own. A move that
+    /// wins is always safe. Deeper traps aren't detected, so a safe move isn't
+    /// necessarily a good one; see [`GameEngine::ranked_moves`] for that.
+    ///
+    /// On the empty board every move is safe. Returns an empty `Vec` if the
+    /// game is over, or if every move loses.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    ///
+    /// // X threatens 2, so anything else loses.
+    /// assert_eq!(game.safe_moves(), vec![2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn safe_moves(&self) -> Vec<usize> {
+        let Some(player) = self.turn() else {
+            return Vec::new();
+        };
+
+        let opponent = player.opponent();
+        let board = Bitboard::from_cells(&self.board);
+        bitboard::indices(board.empty())
+            .filter(|&index| {
+                let next = board.with_move(index, player);
+                if next.state(&self.lines) == Outcome::Win(player) {
+                    return true;
+                }
+
+                // The opponent mustn't be able to win right away...
+                if next.winning_cells(opponent, &self.lines) != 0 {
+                    return false;
+                }
+
+                // ...or to fork when the player has no win to answer with.
+                !bitboard::indices(next.empty()).any(|reply| {
+                    let after = next.with_move(reply, opponent);
+                    after.winning_cells(opponent, &self.lines).count_ones() >= 2
+                        && after.winning_cells(player, &self.lines) == 0
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the winning lines that hold both an X and an O, so that
+    /// neither player can complete them any more.
+    ///
+    /// These are the lines of this game, like [`GameEngine::winning_line`]:
+    /// the [`WINNING_LINES`] by default, with the wrap-around lines of
+    /// [`wrap`](GameEngineBuilder::wrap) or the shorter lines of
+    /// [`win_len`](GameEngineBuilder::win_len) when configured. The list is
+    /// empty on an empty board and grows as the game goes on.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(game.dead_lines().is_empty());
+    ///
+    /// game.simulate_moves(&[0, 1]).unwrap();
+    /// assert_eq!(game.dead_lines(), vec![[0, 1, 2]]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn dead_lines(&self) -> Vec<&[usize]> {
+        let board = Bitboard::from_cells(&self.board);
+        let (x, o) = (board.marks(Player::X), board.marks(Player::O));
+        self.lines_where(|mask| mask & x != 0 && mask & o != 0)
+    }
+
+    /// Returns every winning line that `player` has completed.
+    ///
+    /// A single move can complete two lines at once, so unlike
+    /// [`GameEngine::winning_line`] this lists all of them, e.g. for variant
+    /// scoring. Like [`GameEngine::dead_lines`], it covers the wrap-around and
+    /// shorter lines of a configured game. The list is empty for both players
+    /// while the game is in progress.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// // X's last move at 2 completes the top row and the anti-diagonal.
+    /// game.simulate_moves(&[0, 3, 1, 5, 4, 7, 6, 8, 2]).unwrap();
+    /// assert_eq!(game.completed_lines(Player::X), vec![[0, 1, 2], [2, 4, 6]]);
+    /// assert!(game.completed_lines(Player::O).is_empty());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn completed_lines(&self, player: Player) -> Vec<&[usize]> {
+        let marks = Bitboard::from_cells(&self.board).marks(player);
+        self.lines_where(|mask| mask & !marks == 0)
+    }
+
+    /// Returns the cells of the winning lines whose masks satisfy `keep`.
+    #[cfg(feature = "alloc")]
+    fn lines_where(&self, keep: impl Fn(u16) -> bool) -> Vec<&[usize]> {
+        let masks = self.lines.masks();
+        (0..masks.len())
+            .filter(|&i| keep(masks[i]))
+            .map(|i| self.lines.cells(i))
+            .collect()
+    }
+
+    /// Evaluates the position for `player` by counting the lines they can still complete.
+    ///
+    /// This is a cheap positional heuristic rather than a search. Every winning
+    /// line that contains none of the opponent's marks is open to `player`, and
+    /// is weighted by how many of `player`'s marks it already holds:
+    ///
+    /// | Marks on the line | Weight |
+    /// |-------------------|--------|
+    /// | 0                 | 1      |
+    /// | 1                 | 10     |
+    /// | 2                 | 100    |
+    /// | 3                 | 1000   |
+    ///
+    /// The score is the sum of the weights, so it only compares meaningfully
+    /// between positions and players of the same game. On an empty board both
+    /// players score the same.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.open_line_score(Player::X), 8);
+    /// assert_eq!(game.open_line_score(Player::O), 8);
+    ///
+    /// game.make_move(4).unwrap(); // X takes the center
+    /// // 4 open lines through the center, 4 other open lines.
+    /// assert_eq!(game.open_line_score(Player::X), 4 * 10 + 4);
+    /// // O can only use the 4 lines that avoid the center.
+    /// assert_eq!(game.open_line_score(Player::O), 4);
+    /// ```
+    pub fn open_line_score(&self, player: Player) -> i32 {
+        Bitboard::from_cells(&self.board).open_line_score(player, &self.lines)
+    }
+
+    /// Returns every empty cell where the current player would create a fork:
+    /// two or more simultaneous winning threats.
+    ///
+    /// This works regardless of `ai_enabled`. Returns an empty `Vec` if the
+    /// game is already over.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 1, 4, 8]).unwrap();
+    ///
+    /// // X at 3 threatens 5 and 6; X at 6 threatens 2 and 3.
+    /// assert_eq!(game.fork_candidates(), vec![3, 6]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn fork_candidates(&self) -> Vec<usize> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        let board = Bitboard::from_cells(&self.board);
+        bitboard::indices(board.fork_cells(self.current_player, &self.lines)).collect()
+    }
+
+    /// Recommends a move for the current player and explains why.
+    ///
+    /// The recommendation follows the classic tic-tac-toe strategy, in order:
+    /// win, block the opponent's win, create a fork, block the opponent's fork,
+    /// take the center, take a corner, and otherwise play the best remaining
+    /// move. The [`HintCategory`] of the returned [`Hint`] names the rule that
+    /// applied. When several cells satisfy a rule, the lowest index is used,
+    /// except for fork blocks, which defer to the Minimax AI since naively
+    /// occupying the fork cell can still lose.
+    ///
+    /// Hints are available regardless of `ai_enabled`. Returns `None` if the
+    /// game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, HintCategory};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// let hint = game.hint().unwrap();
+    /// assert_eq!(hint.index, 2);
+    /// assert_eq!(hint.category, HintCategory::Block);
+    /// ```
+    pub fn hint(&self) -> Option<Hint> {
+        if self.is_over() {
+            return None;
+        }
+
+        let board = Bitboard::from_cells(&self.board);
+        let player = self.current_player;
+        let opponent = player.opponent();
+        let first = |cells: u16| bitboard::indices(cells).next();
+        let hint = |index, category| Some(Hint { index, category });
+
+        if let Some(index) = first(board.winning_cells(player, &self.lines)) {
+            return hint(index, HintCategory::Win);
+        }
+        if let Some(index) = first(board.winning_cells(opponent, &self.lines)) {
+            return hint(index, HintCategory::Block);
+        }
+        if let Some(index) = first(board.fork_cells(player, &self.lines)) {
+            return hint(index, HintCategory::Fork);
+        }
+        if board.fork_cells(opponent, &self.lines) != 0 {
+            return hint(
+                self.best_move(self.current_player, UNLIMITED_DEPTH)?,
+                HintCategory::BlockFork,
+            );
+        }
+        if self.board[4] == Cell::Empty {
+            return hint(4, HintCategory::Center);
+        }
+        if let Some(index) = (0..9)
+            .find(|&i| self.board[i] == Cell::Empty && Self::cell_kind(i) == Some(CellKind::Corner))
+        {
+            return hint(index, HintCategory::Corner);
+        }
+        hint(
+            self.best_move(self.current_player, UNLIMITED_DEPTH)?,
+            HintCategory::Neutral,
+        )
+    }
+
+    /// Calculates the best move for the current player using Minimax with pruning.
+    ///
+    /// With a nonzero [`handicap`](GameEngine::handicap), a non-optimal move
+    /// may be returned instead.
+    ///
+    /// Returns:
+    /// - `Some(index)` for the best move when AI is enabled.
+    /// - `None` if the game is over or AI is disabled.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let game = GameEngine::with_ai(false);
+    /// assert_eq!(game.get_best_move(), None); // AI disabled
+    /// ```
+    /// # Example with AI
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// // O should block X's winning move at index 2
+    /// assert_eq!(game.get_best_move(), Some(2));
+    /// ```
+    ///
+    pub fn get_best_move(&self) -> Option<usize> {
+        // If the game is over, no move can be made.
+        // furthermore, if AI is disabled, return None.
+        if !self.ai_enabled || self.is_over() {
+            return None;
+        }
+
+        if self.handicap > 0.0 {
+            // Derive the decision from the seed and the position so it is
+            // reproducible, using a different stream from `randomize_ties`.
+            let board = Bitboard::from_cells(&self.board);
+            let key = board.key(self.current_player) as u64;
+            let mut rng = Rng::new(self.seed.rotate_left(32) ^ key);
+            if rng.chance(self.handicap) {
+                let best_moves = self.best_moves_mask(self.current_player, UNLIMITED_DEPTH);
+                let weaker_moves = board.empty() & !best_moves;
+                if let Some(index) = rng.pick(weaker_moves) {
+                    return Some(index);
+                }
+            }
+        }
+
+        self.best_move(self.current_player, UNLIMITED_DEPTH)
+    }
+
+    /// Calculates the worst move for the current player, the mirror of
+    /// [`GameEngine::get_best_move`]: the move with the lowest minimax score,
+    /// the lowest index if several are equally bad.
+    ///
+    /// This makes a trivially beatable opponent, e.g. for an easy mode that
+    /// helps the player win. The [`handicap`](GameEngine::handicap) and
+    /// [`tie_break`](GameEngine::tie_break) don't apply.
+    ///
+    /// Returns `None` if the game is over or AI is disabled.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    ///
+    /// // O must block at 2; 3 is the first of the moves that lose.
+    /// assert_eq!(game.get_best_move(), Some(2));
+    /// assert_eq!(game.get_worst_move(), Some(3));
+    /// ```
+    pub fn get_worst_move(&self) -> Option<usize> {
+        if !self.ai_enabled || self.is_over() {
+            return None;
+        }
+
+        let scores = self.root_scores(self.current_player, UNLIMITED_DEPTH);
+        (0..9)
+            .filter_map(|i| Some((i, scores[i]?)))
+            .min_by_key(|&(_, score)| score)
+            .map(|(i, _)| i)
+    }
+
+    /// Plays the AI's move for the current player: the move
+    /// [`GameEngine::get_best_move`] suggests is applied right away.
+    ///
+    /// Returns the index played, or `None` if the game is over or AI is
+    /// disabled, in which case the engine is unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, GameEngine};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    ///
+    /// assert_eq!(game.make_ai_move(), Some(2)); // O blocks
+    /// assert_eq!(game.get_board()[2], Cell::O);
+    /// ```
+    pub fn make_ai_move(&mut self) -> Option<usize> {
+        let index = self.get_best_move()?;
+        self.make_move(index)
+            .expect("the AI only suggests legal moves");
+        Some(index)
+    }
+
+    /// Calculates the best move for `player`, regardless of `ai_enabled`.
+    ///
+    /// This decouples "can the AI play" from "what's the best move here", e.g.
+    /// to offer hints to either human in a two-player game. If `player` is not
+    /// the current player, the position is evaluated as if it were their turn.
+    ///
+    /// Returns `None` if the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::with_ai(false);
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// assert_eq!(game.get_best_move(), None); // AI disabled
+    /// assert_eq!(game.get_best_move_for(Player::O), Some(2)); // Block X
+    /// assert_eq!(game.get_best_move_for(Player::X), Some(2)); // Win
+    /// ```
+    pub fn get_best_move_for(&self, player: Player) -> Option<usize> {
+        if self.is_over() {
+            return None;
+        }
+
+        self.best_move(player, UNLIMITED_DEPTH)
+    }
+
+    /// Calculates the best move for the current player, looking at most
+    /// `max_depth` moves ahead.
+    ///
+    /// Positions beyond the depth limit are estimated with
+    /// [`GameEngine::open_line_score`] rather than searched, trading strength
+    /// for speed: a shallow search is fast but can miss deeper tactics. Once
+    /// `max_depth` covers the rest of the game, this plays exactly like
+    /// [`GameEngine::get_best_move`]. A `max_depth` of 0 is treated as 1.
+    ///
+    /// Returns `None` if the game is over or AI is disabled.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// // Looking two moves ahead is enough to see X's threat at 2.
+    /// assert_eq!(game.get_best_move_limited(2), Some(2));
+    /// assert_eq!(game.get_best_move_limited(9), game.get_best_move());
+    /// ```
+    pub fn get_best_move_limited(&self, max_depth: usize) -> Option<usize> {
+        if !self.ai_enabled || self.is_over() {
+            return None;
+        }
+
+        self.best_move(self.current_player, max_depth)
+    }
+
+    /// Calculates the best move for the current player within a time budget,
+    /// by iterative deepening.
+    ///
+    /// Searches of increasing depth, as with [`GameEngine::get_best_move_limited`],
+    /// run until one covers the rest of the game or `budget` has elapsed, and
+    /// the move of the deepest completed search is returned. A search that has
+    /// started always completes, so the call can overrun the budget by one
+    /// search, and at least a one-move search is made. On the 3x3 board the
+    /// full search takes well under a millisecond, so any reasonable budget
+    /// gives perfect play.
+    ///
+    /// Returns `None` if the game is over or AI is disabled.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    ///
+    /// let best = game.get_best_move_timed(Duration::from_secs(1));
+    /// assert_eq!(best, game.get_best_move());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn get_best_move_timed(&self, budget: std::time::Duration) -> Option<usize> {
+        if !self.ai_enabled || self.is_over() {
+            return None;
+        }
+
+        let start = std::time::Instant::now();
+        let remaining = Bitboard::from_cells(&self.board).empty().count_ones() as usize;
+        let mut best = None;
+        for depth in 1..=remaining {
+            best = self.best_move(self.current_player, depth);
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+        best
+    }
+
+    /// Searches for the best move for `player`, as if it were their turn,
+    /// regardless of `ai_enabled`, looking at most `max_depth` moves ahead.
+    ///
+    /// Returns `None` if there are no moves left.
+    fn best_move(&self, player: Player, max_depth: usize) -> Option<usize> {
+        let best_moves = self.best_moves_mask(player, max_depth);
+        let tie_break = if self.randomize_ties {
+            TieBreak::Random
+        } else {
+            self.tie_break
+        };
+
+        let lowest = bitboard::indices(best_moves).next();
+        match tie_break {
+            TieBreak::LowestIndex => lowest,
+            TieBreak::HighestIndex => bitboard::indices(best_moves).last(),
+            TieBreak::PreferCenter => {
+                if best_moves & 1 << 4 != 0 {
+                    Some(4)
+                } else {
+                    lowest
+                }
+            }
+            TieBreak::PreferCorners => bitboard::indices(best_moves)
+                .find(|&i| Self::cell_kind(i) == Some(CellKind::Corner))
+                .or(lowest),
+            TieBreak::Random => {
+                // Derive the choice from the seed and the position so it is reproducible.
+                let board = Bitboard::from_cells(&self.board);
+                let mut rng = Rng::new(self.seed ^ board.key(player) as u64);
+                rng.pick(best_moves)
+            }
+        }
+    }
+
+    /// Returns every optimal move for `player` as a mask of cell indices,
+    /// looking at most `max_depth` moves ahead.
+    fn best_moves_mask(&self, player: Player, max_depth: usize) -> u16 {
+        let mut best_score = -i32::MAX;
+        let mut best_moves = 0;
+
+        for (i, score) in self.root_scores(player, max_depth).into_iter().enumerate() {
+            let Some(score) = score else {
+                continue;
+            };
+
+            // A better score replaces the best moves so far; an equal one joins them.
+            if score > best_score {
+                best_score = score;
+                best_moves = 1 << i;
+            } else if score == best_score {
+                best_moves |= 1 << i;
+            }
+        }
+        best_moves
+    }
+
+    /// Returns every move that is optimal for the current player, in index order.
+    ///
+    /// [`GameEngine::get_best_move`] picks one of these. Returns an empty `Vec`
+    /// if the game is over or AI is disabled.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let game = GameEngine::new();
+    /// // Every opening move draws with perfect play.
+    /// assert_eq!(game.get_best_moves(), (0..9).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn get_best_moves(&self) -> Vec<usize> {
+        if !self.ai_enabled || self.is_over() {
+            return Vec::new();
+        }
+
+        bitboard::indices(self.best_moves_mask(self.current_player, UNLIMITED_DEPTH)).collect()
+    }
+
+    /// Returns `true` if the current player has only one sensible move: either
+    /// a single legal move is left, or a single move is optimal, such as the
+    /// only block that doesn't lose.
+    ///
+    /// UIs can use this to skip a "thinking" delay when the move is obvious.
+    /// It is `false` once the game is over, and available regardless of
+    /// `ai_enabled`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(!game.is_move_forced());
+    ///
+    /// // O must block at 2.
+    /// game.simulate_moves(&[0, 4, 1]).unwrap();
+    /// assert!(game.is_move_forced());
+    /// ```
+    pub fn is_move_forced(&self) -> bool {
+        if self.is_over() {
+            return false;
+        }
+
+        let empty = Bitboard::from_cells(&self.board).empty();
+        empty.count_ones() == 1
+            || self
+                .best_moves_mask(self.current_player, UNLIMITED_DEPTH)
+                .count_ones()
+                == 1
+    }
+
+    /// Returns the minimax score of every legal move for `player`, as if it
+    /// were their turn, indexed by cell, with `None` for occupied cells.
+    ///
+    /// The search looks at most `max_depth` moves ahead, counting the move
+    /// being scored, and estimates positions beyond that with the
+    /// [`open_line_score`](Self::open_line_score) heuristic.
+    fn root_scores(&self, player: Player, max_depth: usize) -> [Option<i32>; 9] {
+        self.search_root(max_depth, &mut Search::new(player))
+    }
+
+    /// Scores every legal move for the maximizing player of `search` like
+    /// [`Self::root_scores`].
+    fn search_root(&self, max_depth: usize, search: &mut Search) -> [Option<i32>; 9] {
+        let mut scores = [None; 9];
+
+        // The maximizing player moves first from the root.
+        let maximizing_player = search.maximizer;
+
+        // The search works on a bitboard, converted once from the public board.
+        let board = Bitboard::from_cells(&self.board);
+
+        // Iterate through each empty cell on the board, skipping moves that are
+        // equivalent under a rotation or reflection to one already evaluated.
+        for i in bitboard::indices(board.distinct_moves()) {
+            // Recursively call the minimax function to evaluate the score of this move.
+            scores[i] = Some(self.minimax_with_pruning(
+                board.with_move(i, maximizing_player),
+                maximizing_player.opponent(),
+                max_depth.saturating_sub(1),
+                -i32::MAX,
+                i32::MAX,
+                search,
+            ));
+        }
+
+        // The skipped moves score the same as the move they are equivalent to.
+        for i in bitboard::indices(board.empty()) {
+            if scores[i].is_none() {
+                scores[i] = board.symmetries().find_map(|symmetry| scores[symmetry[i]]);
+            }
+        }
+        scores
+    }
+
+    /// Returns every legal move paired with its minimax score from the current
+    /// player's perspective.
+    ///
+    /// With the default [`scoring`](GameEngine::scoring), scores are `10` for a
+    /// forced win, `-10` for a forced loss and `0` for a draw under perfect
+    /// play. Moves are listed in index order, and the list
+    /// is empty if the game is over. Scores are available regardless of
+    /// `ai_enabled`.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// let scores = game.move_scores();
+    /// // Blocking at 2 holds the draw; anything else loses.
+    /// assert!(scores.contains(&(2, 0)));
+    /// assert!(scores.iter().filter(|&&(i, _)| i != 2).all(|&(_, s)| s == -10));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn move_scores(&self) -> Vec<(usize, i32)> {
+        if self.is_over() {
+            return Vec::new();
+        }
+
+        self.root_scores(self.current_player, UNLIMITED_DEPTH)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, score)| Some((i, score?)))
+            .collect()
+    }
+
+    /// Returns the result of the game if it is already decided, or `None` if
+    /// it is still open.
+    ///
+    /// - `Some(GameState::Win(player))` if `player` has won or can force a win
+    ///   against any defense.
+    /// - `Some(GameState::Tie)` if the game is tied or must end in a tie
+    ///   however the remaining moves are played.
+    /// - `None` otherwise: perfect play draws, but a mistake could still
+    ///   decide the game.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.forced_outcome(), None);
+    ///
+    /// // X threatens both 2 and 3; O can only block one of them.
+    /// game.simulate_moves(&[0, 1, 4, 8, 6]).unwrap();
+    /// assert_eq!(game.forced_outcome(), Some(GameState::Win(Player::X)));
+    /// ```
+    pub fn forced_outcome(&self) -> Option<GameState> {
+        let state = self.check_state();
+        if state != GameState::InProgress {
+            return Some(state);
+        }
+
+        // Judge the outcome by the standard scores, whatever the AI's preferences.
+        let score = if self.scoring == ScoringConfig::default() {
+            self.root_scores(self.current_player, UNLIMITED_DEPTH)
+        } else {
+            let mut view = self.clone();
+            view.scoring = ScoringConfig::default();
+            view.root_scores(self.current_player, UNLIMITED_DEPTH)
+        }
+        .into_iter()
+        .flatten()
+        .max()?;
+        if score > 0 {
+            Some(GameState::Win(self.current_player))
+        } else if score < 0 {
+            Some(GameState::Win(self.current_player.opponent()))
+        } else if Bitboard::from_cells(&self.board).win_reachable(self.current_player, &self.lines)
+        {
+            None
+        } else {
+            Some(GameState::Tie)
+        }
+    }
+
+    /// Returns `true` if the current player can force a win against any defense.
+    ///
+    /// This is `false` once the game is over, and on the empty board, since
+    /// perfect play by both sides draws. It is available regardless of `ai_enabled`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(!game.can_current_player_win());
+    ///
+    /// // O answers the center with an edge, letting X force a fork.
+    /// game.simulate_moves(&[4, 1]).unwrap();
+    /// assert!(game.can_current_player_win());
+    /// ```
+    pub fn can_current_player_win(&self) -> bool {
+        self.turn().is_some_and(|player| {
+            self.forced_outcome().and_then(|state| state.winner()) == Some(player)
+        })
+    }
+
+    /// Returns `true` if some winning line holds no mark of `player`'s
+    /// opponent and no blocked cell, so `player` could still complete it.
+    ///
+    /// This is much cheaper than a search, and works regardless of whose turn
+    /// it is. Once it is `false` for both players the game can only end in a
+    /// tie. A line `player` has already completed counts as open.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(game.has_winning_path(Player::X));
+    ///
+    /// // Every line now holds both marks, with one cell still to play.
+    /// game.simulate_moves(&[0, 1, 2, 3, 4, 6, 7, 8]).unwrap();
+    /// assert!(!game.has_winning_path(Player::X));
+    /// assert!(!game.has_winning_path(Player::O));
+    /// ```
+    pub fn has_winning_path(&self, player: Player) -> bool {
+        Bitboard::from_cells(&self.board)
+            .open_lines(player, &self.lines)
+            .next()
+            .is_some()
+    }
+
+    /// Returns `true` if the game is in progress but can only end in a tie,
+    /// because no player has a [winning path](GameEngine::has_winning_path)
+    /// left: every line holds marks of both players or a blocked cell.
+    ///
+    /// The remaining moves are then filler, so a UI can fill the board and
+    /// end the game early. This is much cheaper than a search, but doesn't
+    /// see draws that only follow from the order of the remaining moves.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[0, 1, 2, 3, 4, 6, 7]).unwrap();
+    /// assert!(!game.is_drawn_out()); // X could still fill 2, 5 and 8.
+    ///
+    /// game.make_move(8).unwrap();
+    /// assert!(game.is_drawn_out());
+    /// ```
+    pub fn is_drawn_out(&self) -> bool {
+        !self.is_over() && !self.has_winning_path(Player::X) && !self.has_winning_path(Player::O)
+    }
+
+    /// Returns `true` if the current player can hold at least a tie against
+    /// any defense, i.e. they aren't lost with best play.
+    ///
+    /// Unlike [`GameEngine::can_current_player_win`], this is also `true` in
+    /// drawn positions, including the empty board. It is `false` in a
+    /// [losing fork](GameEngine::in_losing_fork), but also in positions that
+    /// are lost more subtly. Once the game is over it is `true` only for a
+    /// tie or an agreed draw. It is available regardless of `ai_enabled`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert!(game.can_hold_draw());
+    ///
+    /// // X threatens both 2 and 3; O can only block one of them.
+    /// game.simulate_moves(&[0, 1, 4, 8, 6]).unwrap();
+    /// assert!(!game.can_hold_draw());
+    /// ```
+    pub fn can_hold_draw(&self) -> bool {
+        if self.is_over() {
+            return self.check_state().winner().is_none();
+        }
+        self.forced_outcome().and_then(|state| state.winner())
+            != Some(self.current_player.opponent())
+    }
+
+    /// Returns the value of the position for the current player, from `-1.0`
+    /// for a forced loss through `0.0` for a draw to `1.0` for a forced win.
+    ///
+    /// This is the [forced outcome](GameEngine::forced_outcome) on a fixed
+    /// scale, for confidence meters that shouldn't depend on the AI's
+    /// [`scoring`](GameEngine::scoring). As the search is exhaustive, the
+    /// value is always one of the three. Once the game is over it reflects the
+    /// result, e.g. `-1.0` for the player on turn after their opponent won.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// assert_eq!(game.confidence(), 0.0);
+    ///
+    /// // X threatens both 2 and 3; O can only block one of them.
+    /// game.simulate_moves(&[0, 1, 4, 8, 6]).unwrap();
+    /// assert_eq!(game.confidence(), -1.0);
+    /// ```
+    pub fn confidence(&self) -> f64 {
+        match self.forced_outcome().and_then(|state| state.winner()) {
+            Some(winner) if winner == self.current_player => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Returns the stubbornest defense when the current player is lost: the
+    /// move after which the opponent, playing perfectly, needs the most moves
+    /// to win.
+    ///
+    /// A lost position scores every move the same for
+    /// [`GameEngine::get_best_move`], so it may pick a move that loses at once.
+    /// This move makes the opponent prove the win, the lowest index among
+    /// equally long defenses. Returns `None` if the current player
+    /// [can hold a draw](GameEngine::can_hold_draw) or the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 1, 0]).unwrap(); // X threatens 8, with a fork to follow
+    ///
+    /// assert!(!game.can_hold_draw());
+    /// assert_eq!(game.best_losing_move(), Some(8));
+    /// ```
+    pub fn best_losing_move(&self) -> Option<usize> {
+        let player = self.turn()?;
+        if self.can_hold_draw() {
+            return None;
+        }
+
+        let board = Bitboard::from_cells(&self.board);
+        let mut best = None;
+        let mut best_score = i32::MIN;
+        for index in bitboard::indices(board.empty()) {
+            let score = -self.stubborn_score(board.with_move(index, player), player.opponent());
+            if score > best_score {
+                best_score = score;
+                best = Some(index);
+            }
+        }
+        best
+    }
+
+    /// Scores `board` for `player`, who is to move, preferring quicker wins
+    /// and slower losses: a decided game scores one more than the number of
+    /// empty cells left, positive for a win and negative for a loss.
+    fn stubborn_score(&self, board: Bitboard, player: Player) -> i32 {
+        let empty = board.empty();
+        let remaining = empty.count_ones() as i32 + 1;
+        match board.state(&self.lines) {
+            Outcome::Win(winner) if winner == player => remaining,
+            Outcome::Win(_) => -remaining,
+            Outcome::Tie => 0,
+            Outcome::InProgress => bitboard::indices(empty)
+                .map(|index| {
+                    -self.stubborn_score(board.with_move(index, player), player.opponent())
+                })
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Counts the positions searched to solve the game from here, with and
+    /// without the AI's optimizations, as `(with_pruning, without_pruning)`.
+    ///
+    /// `with_pruning` is the number of positions the AI's search evaluates,
+    /// with alpha-beta pruning, skipping symmetric moves and, with the `std`
+    /// feature, reusing positions reached through different move orders.
+    /// `without_pruning` is the size of the full game tree: every position
+    /// reachable from here. Neither count includes the current position, and
+    /// both are 0 once the game is over.
+    ///
+    /// This is a diagnostic, e.g. for teaching about game-tree complexity.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let game = GameEngine::new();
+    /// let (with_pruning, without_pruning) = game.count_nodes();
+    /// assert_eq!(without_pruning, 549_945);
+    /// assert!(with_pruning < without_pruning / 100);
+    /// ```
+    pub fn count_nodes(&self) -> (u64, u64) {
+        if self.is_over() {
+            return (0, 0);
+        }
+
+        let mut search = Search::new(self.current_player);
+        self.search_root(UNLIMITED_DEPTH, &mut search);
+        let board = Bitboard::from_cells(&self.board);
+        (
+            search.nodes,
+            board.tree_size(self.current_player, &self.lines),
+        )
+    }
+
+    /// Counts the distinct complete games that can be played from here: every
+    /// sequence of legal moves that ends in a win or a tie.
+    ///
+    /// A finished game counts as one. From the empty board this is the
+    /// well-known 255,168 games of tic-tac-toe, without merging games that are
+    /// rotations or reflections of each other.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// assert_eq!(GameEngine::new().count_games_from(), 255_168);
+    /// ```
+    pub fn count_games_from(&self) -> u64 {
+        if self.is_over() {
+            return 1;
+        }
+
+        Bitboard::from_cells(&self.board).game_count(self.current_player, &self.lines)
+    }
+
+    /// Counts the distinct positions that can be reached from here, including
+    /// the current one.
+    ///
+    /// Unlike [`GameEngine::count_games_from`], positions reached through
+    /// different move orders are counted once, as are positions with the same
+    /// board and player to move. Rotations and reflections are still counted
+    /// separately. From the empty board this is the well-known 5,478 legal
+    /// positions of tic-tac-toe; a finished game counts as 1.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// assert_eq!(GameEngine::new().reachable_state_count(), 5_478);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn reachable_state_count(&self) -> u64 {
+        let mut visited = BTreeSet::new();
+        self.clone().visit_positions(&mut visited);
+        visited.len() as u64
+    }
+
+    /// Adds the key of this position and every position reachable from it to
+    /// `visited`, skipping positions already in it.
+    #[cfg(feature = "alloc")]
+    fn visit_positions(&mut self, visited: &mut BTreeSet<u32>) {
+        if !visited.insert(self.position_key()) || self.is_over() {
+            return;
+        }
+
+        for index in 0..9 {
+            if let Ok(tok) = self.make_move_tok(index) {
+                self.visit_positions(visited);
+                self.undo_tok(tok);
+            }
+        }
+    }
+
+    /// Returns every legal move ordered from best to worst for the current player.
+    ///
+    /// Moves are sorted by descending [`GameEngine::move_scores`] score, with
+    /// ties broken by the lower index. Returns an empty `Vec` if the game is over.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.make_move(0).unwrap(); // X
+    /// game.make_move(4).unwrap(); // O
+    /// game.make_move(1).unwrap(); // X
+    ///
+    /// // Only the block at 2 holds the draw, so it comes first.
+    /// assert_eq!(game.ranked_moves(), vec![2, 3, 5, 6, 7, 8]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn ranked_moves(&self) -> Vec<usize> {
+        let mut scores = self.move_scores();
+        // The scores are in index order and the sort is stable, so ties keep it.
+        scores.sort_by_key(|&(_, score)| core::cmp::Reverse(score));
+        scores.into_iter().map(|(index, _)| index).collect()
+    }
+
+    /// Returns the principal variation: the moves both sides would play with
+    /// best play from here until the game ends, starting with the current
+    /// player's move.
+    ///
+    /// Each move is the one [`GameEngine::get_best_move`] picks in the position
+    /// reached, so the line follows the engine's tie-breaking and
+    /// [`scoring`](GameEngine::scoring), but not its `handicap`. This is the
+    /// "if you play here, the game goes ..." line of an analysis panel. As the
+    /// AI scores every win alike, a won line isn't necessarily the quickest. The
+    /// list is empty if the game is over. It is available regardless of
+    /// `ai_enabled`.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// game.simulate_moves(&[4, 1]).unwrap();
+    ///
+    /// // X can force a win, starting in the corner.
+    /// let line = game.principal_variation();
+    /// assert_eq!(line[0], 0);
+    ///
+    /// game.simulate_moves(&line).unwrap();
+    /// assert_eq!(game.check_state(), GameState::Win(Player::X));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn principal_variation(&self) -> Vec<usize> {
+        let mut game = self.clone();
+        let mut line = Vec::new();
+        while !game.is_over() {
+            let Some(index) = game.best_move(game.current_player, UNLIMITED_DEPTH) else {
+                break;
+            };
+            game.place(index);
+            line.push(index);
+        }
+        line
+    }
+
+    /// Returns the canonical first moves paired with their minimax scores, a
+    /// compact reference for how to open.
+    ///
+    /// On the empty board every opening is a rotation or reflection of one of
+    /// three: a corner, an edge or the center, listed as `0`, `1` and `4`.
+    /// With the default [`scoring`](GameEngine::scoring) they all score `0`,
+    /// since perfect tic-tac-toe is a draw whichever cell is taken first.
+    ///
+    /// Once moves have been played, this returns every legal move with its
+    /// score, ordered like [`GameEngine::ranked_moves`]. The list is empty if
+    /// the game is over. Scores are available regardless of `ai_enabled`.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let game = GameEngine::new();
+    /// assert_eq!(game.opening_book(), vec![(0, 0), (1, 0), (4, 0)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn opening_book(&self) -> Vec<(usize, i32)> {
+        let mut scores = self.move_scores();
+        if self.is_empty() {
+            let distinct = Bitboard::from_cells(&self.board).distinct_moves();
+            scores.retain(|&(index, _)| distinct & (1 << index) != 0);
+        }
+        scores.sort_by_key(|&(_, score)| core::cmp::Reverse(score));
+        scores
+    }
+
+    /// Plays a uniformly random legal move for the current player.
+    ///
+    /// The move is chosen with the engine's pseudo-random number generator,
+    /// so engines with the same [`seed`](GameEngine::seed) play the same
+    /// sequence of random moves. This makes a handy baseline opponent and a
+    /// simple way to fuzz the engine with random games.
+    ///
+    /// Returns the index played, or `None` if the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let mut game = GameEngine::builder().seed(42).build();
+    /// while let Some(index) = game.play_random_move() {
+    ///     assert_eq!(game.last_move(), Some(index));
+    /// }
+    /// assert!(game.is_over());
+    /// ```
+    pub fn play_random_move(&mut self) -> Option<usize> {
+        if self.is_over() {
+            return None;
+        }
+
+        let empty = Bitboard::from_cells(&self.board).empty();
+        let index = self.rng.pick(empty)?;
+        self.make_move(index)
+            .expect("random moves are picked from empty cells");
+        Some(index)
+    }
+
+    /// Plays the game to the end with the AI making every move for both sides.
+    ///
+    /// The AI is temporarily enabled for the duration of the call, so this also
+    /// works on Human vs Human engines; `ai_enabled` is restored afterwards.
+    ///
+    /// Returns the terminal [`GameState`]. Since the AI plays perfectly, a game
+    /// played out from an empty board always ends in a tie.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState};
+    ///
+    /// let mut game = GameEngine::with_ai(false);
+    /// assert_eq!(game.play_out(), GameState::Tie);
+    /// assert!(game.is_over());
+    /// ```
+    pub fn play_out(&mut self) -> GameState {
+        let ai_enabled = self.ai_enabled;
+        self.ai_enabled = true;
+
+        while self.make_ai_move().is_some() {}
+
+        self.ai_enabled = ai_enabled;
+        self.check_state()
+    }
+
+    /// Finishes the game with optimal moves for both sides and returns the
+    /// terminal [`GameState`], e.g. to show the outcome after a resignation.
+    ///
+    /// Unlike [`GameEngine::play_out`], which plays whatever
+    /// [`GameEngine::get_best_move`] suggests, every move is optimal whatever
+    /// the engine's [`handicap`](GameEngine::handicap), and `ai_enabled` is
+    /// neither needed nor touched. The engine is left in the final position;
+    /// a finished game is returned as it is.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameEngine, GameState, Player};
+    ///
+    /// let mut game = GameEngine::builder().ai(false).handicap(1.0).build();
+    /// game.simulate_moves(&[4, 1]).unwrap(); // O's edge reply loses
+    ///
+    /// assert_eq!(game.finish_with_ai(), GameState::Win(Player::X));
+    /// assert!(game.is_over());
+    /// assert!(!game.ai_enabled);
+    /// ```
+    pub fn finish_with_ai(&mut self) -> GameState {
+        while !self.is_over() {
+            let index = self
+                .best_move(self.current_player, UNLIMITED_DEPTH)
+                .expect("a game in progress has empty cells");
+            self.make_move(index)
+                .expect("the AI only suggests legal moves");
+        }
+        self.check_state()
+    }
+
+    /// Plays the game to the end with `x` choosing X's moves and `o` choosing
+    /// O's, and returns the terminal [`GameState`].
+    ///
+    /// This pits strategies against each other, e.g. for benchmarking or
+    /// testing. `ai_enabled` plays no part; a [`Minimax`](crate::Minimax)
+    /// strategy plays perfectly either way.
+    ///
+    /// # Panics
+    /// Panics if a strategy chooses an illegal move.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{FirstAvailable, GameEngine, GameState, Minimax, Player};
+    ///
+    /// let mut game = GameEngine::new();
+    /// let result = game.play_out_with(&mut FirstAvailable, &mut Minimax);
+    /// assert_eq!(result, GameState::Win(Player::O));
+    /// ```
+    pub fn play_out_with(&mut self, x: &mut dyn Strategy, o: &mut dyn Strategy) -> GameState {
+        while !self.is_over() {
+            let index = match self.current_player {
+                Player::X => x.choose(self),
+                Player::O => o.choose(self),
+            };
+            self.make_move(index)
+                .expect("strategies must choose legal moves");
+        }
+        self.check_state()
+    }
+
+    /// Estimates how the game ends when the current player plays perfectly and
+    /// the opponent plays uniformly random moves, as the fractions of
+    /// `(wins, draws, losses)` for the current player over `samples`
+    /// simulated games.
+    ///
+    /// Against a perfect opponent every game from the empty board is drawn;
+    /// this gives a more realistic "you'll probably win" figure against a
+    /// casual one. The opponent's moves are drawn from a generator seeded
+    /// with the engine's [`seed`](GameEngine::seed), so the same seed always
+    /// gives the same result. Returns all zeros if `samples` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GameEngine;
+    ///
+    /// let game = GameEngine::builder().seed(7).build();
+    /// let (wins, draws, losses) = game.outcome_distribution_vs_random(20);
+    ///
+    /// // Perfect play never loses, and usually beats random moves.
+    /// assert_eq!(losses, 0.0);
+    /// assert!(wins > draws);
+    /// assert_eq!(wins + draws, 1.0);
+    /// ```
+    pub fn outcome_distribution_vs_random(&self, samples: usize) -> (f64, f64, f64) {
+        if samples == 0 {
+            return (0.0, 0.0, 0.0);
+        }
+
+        let player = self.current_player;
+        let mut opponent = Random::new(self.seed);
+        let (mut wins, mut draws, mut losses) = (0, 0, 0);
+        for _ in 0..samples {
+            let mut game = self.clone();
+            let result = match player {
+                Player::X => game.play_out_with(&mut Minimax, &mut opponent),
+                Player::O => game.play_out_with(&mut opponent, &mut Minimax),
+            };
+            match result.winner() {
+                Some(winner) if winner == player => wins += 1,
+                Some(_) => losses += 1,
+                None => draws += 1,
+            }
+        }
+
+        let samples = samples as f64;
+        (
+            wins as f64 / samples,
+            draws as f64 / samples,
+            losses as f64 / samples,
+        )
+    }
+
+    /// The Minimax algorithm with Alpha-Beta pruning, implemented recursively.
+    ///
+    /// This is a private helper method that evaluates the game tree to find the
+    /// best possible move.
+    /// - `board`: The current state of the game board, as a bitboard.
+    /// - `player`: The player whose turn it is to evaluate.
+    /// - `alpha`: The best score for the maximizing player.
+    /// - `beta`: The best score for the minimizing player.
+    /// - `search`: The maximizing player and the positions already searched.
+    ///
+    /// Returns an integer score for the current board state, from the point
+    /// of view of the maximizing player.
+    fn minimax_with_pruning(
+        &self,
+        board: Bitboard,
+        player: Player,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        search: &mut Search,
+    ) -> i32 {
+        search.nodes += 1;
+
+        // Check the state of the board and return a score if the game is over.
+        match board.state(&self.lines) {
+            Outcome::Win(winner) => {
+                // Score the outcome from the maximizing player's point of view.
+                return if winner == search.maximizer {
+                    self.scoring.win
+                } else {
+                    self.scoring.loss
+                };
+            }
+            Outcome::Tie => return self.scoring.tie,
+            Outcome::InProgress => {}
+        }
+
+        // Out of depth: estimate the position instead of searching further.
+        if depth == 0 {
+            return self.heuristic_score(board, search.maximizer);
+        }
+
+        // Reuse a stored evaluation of this position, narrowing the window by
+        // its bound when the score isn't exact.
+        let (original_alpha, original_beta) = (alpha, beta);
+        if let Some(entry) = search.table.get(&board, player) {
+            match entry.bound {
+                Bound::Exact => return entry.score,
+                Bound::Lower => alpha = alpha.max(entry.score),
+                Bound::Upper => beta = beta.min(entry.score),
+            }
+            if beta <= alpha {
+                return entry.score;
+            }
+        }
+
+        let score = self.search_moves(board, player, depth, alpha, beta, search);
+
+        // Record whether the search completed inside the window or was cut off.
+        let bound = if score <= original_alpha {
+            Bound::Upper
+        } else if score >= original_beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        search.table.insert(&board, player, Entry { score, bound });
+        score
+    }
+
+    /// Searches every move available to `player` for [`Self::minimax_with_pruning`].
+    fn search_moves(
+        &self,
+        board: Bitboard,
+        player: Player,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        search: &mut Search,
+    ) -> i32 {
+        // The current player is either the maximizing or minimizing player in this subtree.
+        let current_player_is_maximizing = player == search.maximizer;
+
+        if current_player_is_maximizing {
+            let mut max_eval = -i32::MAX;
+            for move_index in board.ordered_moves(player, &self.lines) {
+                // Simulate the move and recursively call minimax for the opponent.
+                let eval = self.minimax_with_pruning(
+                    board.with_move(move_index, player),
+                    player.opponent(),
+                    depth - 1,
+                    alpha,
+                    beta,
+                    search,
+                );
+
+                // Update the maximum score.
+                max_eval = max_eval.max(eval);
+
+                // Update alpha for the maximizing player.
+                alpha = alpha.max(eval);
+
+                // Alpha-beta pruning condition.
+                if beta <= alpha {
+                    break;
+                }
+            }
+            max_eval
+        } else {
+            let mut min_eval = i32::MAX;
+            for move_index in board.ordered_moves(player, &self.lines) {
+                // Simulate the move and recursively call minimax for the opponent.
+                let eval = self.minimax_with_pruning(
+                    board.with_move(move_index, player),
+                    player.opponent(),
+                    depth - 1,
+                    alpha,
+                    beta,
+                    search,
+                );
+
+                // Update the minimum score.
+                min_eval = min_eval.min(eval);
+
+                // Update beta for the minimizing player.
+                beta = beta.min(eval);
+
+                // Alpha-beta pruning condition.
+                if beta <= alpha {
+                    break;
+                }
+            }
+            min_eval
+        }
+    }
+
+    /// Estimates an unfinished position for the depth-limited search, from the
+    /// perspective of `maximizer`.
+    ///
+    /// The difference in [`open_line_score`](Self::open_line_score) between the
+    /// players is clamped strictly between the loss and win scores of
+    /// [`scoring`](Self::scoring), so a position that is actually decided
+    /// always outweighs an estimate.
+    fn heuristic_score(&self, board: Bitboard, maximizer: Player) -> i32 {
+        let own = board.open_line_score(maximizer, &self.lines);
+        let theirs = board.open_line_score(maximizer.opponent(), &self.lines);
+        let bound = self.scoring.win.min(self.scoring.loss.saturating_neg()) - 1;
+        (own - theirs).clamp(-bound.max(0), bound.max(0))
+    }
+
+    /// A helper function to check the state of a given board by scanning every line.
+    /// The Minimax algorithm uses the faster [`Bitboard::state`] instead.
+    fn check_board_state(&self, board: [Cell; 9]) -> GameState {
+        // Iterate through each winning line to check for a win. In a standard
+        // game these are `STANDARD_LINES`, built from `WINNING_LINES`.
+        for &line in self.lines.masks() {
+            let mut cells = bitboard::indices(line).map(|i| board[i]);
+            let first = cells.next().unwrap_or(Cell::Empty);
+
+            // Determine the player who could own the line from its first cell;
+            // a line starting with an empty cell can't be complete.
+            let player = match first {
+                Cell::X => Player::X,
+                Cell::O => Player::O,
+                Cell::Empty | Cell::Blocked => continue,
+            };
+
+            // If the cells are all the same, we have a winner. On a board that
+            // can't occur in play, with lines for both players, the first wins.
+            if cells.all(|cell| cell == first) {
+                return GameState::Win(player);
+            }
+        }
+
+        // If no winner is found, check if the board is full.
+        if !board.contains(&Cell::Empty) {
+            return GameState::Tie;
+        }
+
+        // If neither a win nor a tie, the game is still ongoing.
+        GameState::InProgress
+    }
+}
+
+impl TryFrom<[Cell; 9]> for GameEngine {
+    type Error = BoardError;
+
+    /// Creates a game engine from a board. See [`GameEngine::from_board`].
+    fn try_from(board: [Cell; 9]) -> Result<Self, Self::Error> {
+        Self::from_board(board)
+    }
+}
+
+impl TryFrom<&[Cell]> for GameEngine {
+    type Error = BoardError;
+
+    /// Creates a game engine from a slice of exactly 9 cells.
+    /// See [`GameEngine::from_board`].
+    fn try_from(cells: &[Cell]) -> Result<Self, Self::Error> {
+        let board: [Cell; 9] = cells
+            .try_into()
+            .map_err(|_| BoardError::InvalidLength(cells.len()))?;
+        Self::from_board(board)
+    }
+}
+
+impl fmt::Display for GameEngine {
+    /// Draws the board as three rows of cells, top to bottom, using the
+    /// characters chosen with [`GameEngine::set_symbols`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [x, o, empty] = self.symbols;
+        for (row, cells) in self.board.chunks(3).enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for cell in cells {
+                let symbol = match cell {
+                    Cell::X => x,
+                    Cell::O => o,
+                    Cell::Empty => empty,
+                    Cell::Blocked => '#',
+                };
+                write!(f, "{}", symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*; // Import everything from this module
 
+    impl GameEngine {
+        /// Plays each move in order, panicking on an illegal one.
+        fn simulate(&mut self, moves: &[usize]) {
+            self.simulate_moves(moves).unwrap();
+        }
+    }
+
+    #[test]
+    fn x_can_win() {
+        let mut game = GameEngine::new();
+        game.make_move(0).unwrap(); // X
+        game.make_move(3).unwrap(); // O
+        game.make_move(1).unwrap(); // X
+        game.make_move(4).unwrap(); // O
+        game.make_move(2).unwrap(); // X wins
+        assert_eq!(game.check_state(), GameState::Win(Player::X));
+    }
+
+    #[test]
+    fn tie_game() {
+        let mut game = GameEngine::new();
+        let moves = [0, 1, 2, 4, 3, 5, 7, 6, 8];
+        for &i in &moves {
+            game.make_move(i).unwrap();
+        }
+        assert_eq!(game.check_state(), GameState::Tie);
+    }
+
+    #[test]
+    fn invalid_move_out_of_bounds() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.make_move(9), Err(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn invalid_move_occupied() {
+        let mut game = GameEngine::new();
+        game.make_move(0).unwrap();
+        assert_eq!(game.make_move(0), Err(MoveError::CellOccupied));
+    }
+
+    #[test]
+    fn minimax_ai_blocks_win() {
+        let mut game = GameEngine::new();
+        game.make_move(0).unwrap(); // X
+        game.make_move(4).unwrap(); // O
+        game.make_move(1).unwrap(); // X
+        // O (AI) should now block X at 2
+        assert_eq!(game.get_best_move(), Some(2));
+    }
+
+    #[test]
+    fn winning_moves_for_both_players() {
+        let mut game = GameEngine::new();
+        assert!(game.winning_moves_for(Player::X).is_empty());
+        game.make_move(0).unwrap(); // X
+        game.make_move(3).unwrap(); // O
+        game.make_move(1).unwrap(); // X
+        game.make_move(4).unwrap(); // O
+        // X threatens 2, O threatens 5, regardless of whose turn it is.
+        assert_eq!(game.winning_moves_for(Player::X), vec![2]);
+        assert_eq!(game.winning_moves_for(Player::O), vec![5]);
+        game.make_move(2).unwrap(); // X wins
+        assert!(game.winning_moves_for(Player::O).is_empty());
+    }
+
+    #[test]
+    fn ai_self_play_always_ties() {
+        let mut game = GameEngine::with_ai(false);
+        assert_eq!(game.play_out(), GameState::Tie);
+        assert!(!game.ai_enabled);
+
+        // Starting from any opening move, perfect play still ends in a tie.
+        for opening in 0..9 {
+            let mut game = GameEngine::new();
+            game.make_move(opening).unwrap();
+            assert_eq!(game.play_out(), GameState::Tie);
+        }
+    }
+
+    #[test]
+    fn o_can_start() {
+        let mut game = GameEngine::with_starting_player(Player::O, true);
+        assert_eq!(game.current_player, Player::O);
+        game.make_move(0).unwrap();
+        assert_eq!(game.get_board()[0], Cell::O);
+        assert_eq!(game.current_player, Player::X);
+        assert_eq!(game.play_out(), GameState::Tie);
+    }
+
+    #[test]
+    fn grid_is_row_major() {
+        let mut game = GameEngine::new();
+        game.make_move(0).unwrap(); // X
+        game.make_move(8).unwrap(); // O
+        game.make_move(3).unwrap(); // X
+        let grid = game.get_grid();
+        assert_eq!(grid[0][0], Cell::X);
+        assert_eq!(grid[1][0], Cell::X);
+        assert_eq!(grid[2][2], Cell::O);
+        assert_eq!(grid[0][1], Cell::Empty);
+    }
+
+    #[test]
+    fn undo_and_redo_moves() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.undo_move(), Err(MoveError::NothingToUndo));
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+
+        game.make_move(0).unwrap(); // X
+        game.make_move(4).unwrap(); // O
+        assert_eq!(game.undo_move(), Ok(4));
+        assert_eq!(game.undo_move(), Ok(0));
+        assert_eq!(game.current_player, Player::X);
+        assert!(game.get_board().iter().all(|&c| c == Cell::Empty));
+
+        assert_eq!(game.redo_move(), Ok(0));
+        assert_eq!(game.redo_move(), Ok(4));
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+        assert_eq!(game.get_board()[4], Cell::O);
+
+        // Undo still works after a redo.
+        assert_eq!(game.undo_move(), Ok(4));
+        assert_eq!(game.current_player, Player::O);
+    }
+
+    #[test]
+    fn new_move_clears_redo() {
+        let mut game = GameEngine::new();
+        game.make_move(0).unwrap(); // X
+        game.make_move(4).unwrap(); // O
+        game.undo_move().unwrap();
+        game.make_move(8).unwrap(); // O plays elsewhere
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+        assert_eq!(game.get_board()[4], Cell::Empty);
+        assert_eq!(game.get_board()[8], Cell::O);
+    }
+
+    #[test]
+    fn last_move_follows_undo_and_redo() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.last_move(), None);
+        game.make_move(0).unwrap();
+        game.make_move(4).unwrap();
+        assert_eq!(game.last_move(), Some(4));
+        game.undo_move().unwrap();
+        assert_eq!(game.last_move(), Some(0));
+        game.undo_move().unwrap();
+        assert_eq!(game.last_move(), None);
+        game.redo_move().unwrap();
+        assert_eq!(game.last_move(), Some(0));
+    }
+
+    #[test]
+    fn hints_explain_recommended_moves() {
+        let mut game = GameEngine::with_ai(false);
+        let category = |game: &GameEngine| game.hint().map(|h| (h.index, h.category));
+        assert_eq!(category(&game), Some((4, HintCategory::Center)));
+
+        game.make_move(4).unwrap(); // X
+        assert_eq!(category(&game), Some((0, HintCategory::Corner)));
+
+        // X in opposite corners threatens forks at 2 and 6; O must play an edge.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 8]);
+        assert_eq!(category(&game), Some((1, HintCategory::BlockFork)));
+
+        // X can fork at 3 (threatening 5 and 6).
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 8, 4, 1]);
+        assert_eq!(category(&game), Some((3, HintCategory::Fork)));
+
+        game.simulate(&[3, 6]);
+        assert_eq!(category(&game), Some((5, HintCategory::Win)));
+
+        game.simulate(&[5]);
+        assert_eq!(game.hint(), None);
+    }
+
+    #[test]
+    fn best_move_for_either_player() {
+        let mut game = GameEngine::with_ai(false);
+        game.simulate(&[0, 6, 4]);
+        // O to move: O must block at 8, while X could fork at 1.
+        assert_eq!(game.get_best_move_for(Player::O), Some(8));
+        assert_eq!(game.get_best_move_for(Player::X), Some(1));
+        assert_eq!(game.current_player, Player::O);
+
+        game.simulate(&[1, 8]);
+        assert_eq!(game.get_best_move_for(Player::O), None);
+    }
+
+    #[test]
+    fn move_scores_rate_every_legal_move() {
+        let mut game = GameEngine::new();
+        let scores = game.move_scores();
+        assert_eq!(scores.len(), 9);
+        assert!(scores.iter().all(|&(_, score)| score == 0));
+
+        game.simulate(&[0, 4, 2, 1, 3]);
+        // O wins at 7; blocking X at 6 only draws.
+        let scores = game.move_scores();
+        assert_eq!(
+            scores.iter().map(|&(i, _)| i).collect::<Vec<_>>(),
+            [5, 6, 7, 8]
+        );
+        assert!(scores.contains(&(7, 10)));
+        assert!(scores.contains(&(6, 0)));
+        assert!(scores.contains(&(5, -10)));
+
+        game.simulate(&[7]);
+        assert!(game.move_scores().is_empty());
+    }
+
+    #[test]
+    fn randomized_ties_stay_optimal_and_reproducible() {
+        let openings: Vec<_> = (0..20)
+            .map(|seed| {
+                let game = GameEngine::builder()
+                    .seed(seed)
+                    .randomize_ties(true)
+                    .build();
+                assert_eq!(game.get_best_move(), game.get_best_move());
+                game.get_best_move().unwrap()
+            })
+            .collect();
+        assert!(openings.iter().any(|&i| i != openings[0]));
+
+        // Randomizing never picks a suboptimal move.
+        let mut game = GameEngine::builder().seed(3).randomize_ties(true).build();
+        game.simulate(&[0, 4, 1]);
+        assert_eq!(game.get_best_moves(), [2]);
+        assert_eq!(game.get_best_move(), Some(2));
+
+        let mut game = GameEngine::builder().seed(3).randomize_ties(true).build();
+        assert_eq!(game.play_out(), GameState::Tie);
+    }
+
+    #[test]
+    fn valid_moves() {
+        let mut game = GameEngine::new();
+        assert!((0..9).all(|i| game.is_valid_move(i)));
+        assert!(!game.is_valid_move(9));
+
+        game.simulate(&[0, 3, 1, 4]);
+        assert!(!game.is_valid_move(0));
+        assert!(game.is_valid_move(2));
+
+        // Once X wins, no cell is playable.
+        game.simulate(&[2]);
+        assert!(!game.is_valid_move(5));
+    }
+
+    #[test]
+    fn counts_marks() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.mark_counts(), (0, 0));
+        game.simulate(&[0, 1, 2, 4, 3]);
+        assert_eq!(game.mark_counts(), (3, 2));
+
+        let mut game = GameEngine::with_starting_player(Player::O, false);
+        game.simulate(&[4]);
+        assert_eq!(game.mark_counts(), (0, 1));
+    }
+
+    #[test]
+    fn on_move_fires_after_each_move() {
+        use std::sync::{Arc, Mutex};
+
+        let moves = Arc::new(Mutex::new(Vec::new()));
+        let mut game = GameEngine::new();
+        let recorded = Arc::clone(&moves);
+        game.set_on_move(Box::new(move |player, index| {
+            recorded.lock().unwrap().push((player, index));
+        }));
+
+        game.simulate(&[4, 0]);
+        assert_eq!(game.make_move(4), Err(MoveError::CellOccupied));
+        game.undo_move().unwrap();
+        game.redo_move().unwrap();
+        assert_eq!(
+            *moves.lock().unwrap(),
+            [(Player::X, 4), (Player::O, 0), (Player::O, 0)]
+        );
+
+        // Clones don't carry the callback.
+        let mut clone = game.clone();
+        clone.make_move(8).unwrap();
+        assert_eq!(moves.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn ranked_moves_best_first() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.ranked_moves(), (0..9).collect::<Vec<_>>());
+
+        game.simulate(&[0, 4, 2, 1, 3]);
+        // Winning at 7 beats blocking at 6, which beats losing at 5 or 8.
+        assert_eq!(game.ranked_moves(), [7, 6, 5, 8]);
+
+        game.simulate(&[7]);
+        assert!(game.ranked_moves().is_empty());
+    }
+
+    #[test]
+    fn turn_is_none_once_over() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.turn(), Some(Player::X));
+        game.simulate(&[0]);
+        assert_eq!(game.turn(), Some(Player::O));
+
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        assert_eq!(game.check_state(), GameState::Tie);
+        assert_eq!(game.turn(), None);
+    }
+
+    #[test]
+    fn shorter_win_length() {
+        let mut game = GameEngine::builder().win_len(2).build();
+        assert_eq!(game.win_len(), 2);
+        game.simulate(&[0, 8]);
+        assert_eq!(game.winning_moves_for(Player::X), [1, 3, 4]);
+        assert_eq!(game.get_best_move(), Some(1));
+        game.simulate(&[1]);
+        assert_eq!(game.check_state(), GameState::Win(Player::X));
+
+        // Diagonal pairs off the main diagonals count too.
+        let mut game = GameEngine::builder().win_len(2).ai(false).build();
+        game.simulate(&[1, 0, 5]);
+        assert_eq!(game.check_state(), GameState::Win(Player::X));
+    }
+
+    #[test]
+    fn cells_yield_indices_in_order() {
+        let mut game = GameEngine::new();
+        game.simulate(&[2, 6]);
+        let cells: Vec<_> = game.cells().collect();
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[0], (0, Cell::Empty));
+        assert_eq!(cells[2], (2, Cell::X));
+        assert_eq!(cells[6], (6, Cell::O));
+    }
+
+    #[test]
+    fn simulate_moves_stops_at_first_invalid_move() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.simulate_moves(&[0, 1, 2, 4, 3, 5, 7, 6, 8]), Ok(()));
+        assert_eq!(game.check_state(), GameState::Tie);
+
+        let mut game = GameEngine::new();
+        assert_eq!(
+            game.simulate_moves(&[0, 4, 9, 8]),
+            Err((2, MoveError::OutOfBounds))
+        );
+        assert_eq!(game.last_move(), Some(4));
+        assert_eq!(game.get_board()[8], Cell::Empty);
+    }
+
+    #[test]
+    fn forced_outcomes() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.forced_outcome(), None);
+
+        // O answered a corner with an edge; X forces a win.
+        game.simulate(&[0, 1]);
+        assert_eq!(game.forced_outcome(), Some(GameState::Win(Player::X)));
+
+        // Every line is blocked for both players: a tie is certain.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 8, 1, 7, 6, 2, 5]);
+        assert_eq!(game.forced_outcome(), Some(GameState::Tie));
+
+        game.simulate(&[3]);
+        assert_eq!(game.forced_outcome(), Some(GameState::Tie));
+    }
+
+    #[test]
+    fn engine_from_board() {
+        use Cell::{Empty as E, O, X};
+
+        // O must answer opposite corners with an edge.
+        let game = GameEngine::try_from([X, E, E, E, O, E, E, E, X]).unwrap();
+        assert_eq!(game.current_player, Player::O);
+        assert_eq!(game.get_best_move(), Some(1));
+
+        let cells: &[Cell] = &[X, X, X, O, O, E, E, E, E];
+        let game = GameEngine::try_from(cells).unwrap();
+        assert_eq!(game.check_state(), GameState::Win(Player::X));
+
+        let short: &[Cell] = &[X, O];
+        assert_eq!(
+            GameEngine::try_from(short).err(),
+            Some(BoardError::InvalidLength(2))
+        );
+        assert_eq!(
+            GameEngine::try_from([O, E, E, E, E, E, E, E, E]).err(),
+            Some(BoardError::InvalidMarkCounts { x: 0, o: 1 })
+        );
+        assert_eq!(
+            GameEngine::try_from([X, X, X, O, O, O, X, E, E]).err(),
+            Some(BoardError::MultipleWinners)
+        );
+        // O kept playing after X completed the top row.
+        assert_eq!(
+            GameEngine::try_from([X, X, X, O, O, E, O, E, E]).err(),
+            Some(BoardError::MoveAfterWin)
+        );
+    }
+
+    #[test]
+    fn random_moves_are_reproducible() {
+        let random_game = |seed| {
+            let mut game = GameEngine::builder().seed(seed).build();
+            while game.play_random_move().is_some() {}
+            assert!(game.is_over());
+            (*game.get_board(), game.last_move())
+        };
+        assert_eq!(random_game(1), random_game(1));
+        assert!((2..10).any(|seed| random_game(seed) != random_game(1)));
+    }
+
+    #[test]
+    fn board_symmetries() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 5]);
+        let board = *game.get_board();
+
+        let mut rotated = board;
+        for _ in 0..4 {
+            rotated = Symmetry::Rotate90.apply(&rotated);
+            assert_eq!(
+                rotated == board,
+                rotated == Symmetry::Identity.apply(&board)
+            );
+        }
+        assert_eq!(rotated, board);
+        assert_eq!(
+            game.transform(Symmetry::Rotate180),
+            Symmetry::Rotate90.apply(&game.transform(Symmetry::Rotate90))
+        );
+
+        // Reflections are their own inverse.
+        for symmetry in [
+            Symmetry::FlipHorizontal,
+            Symmetry::FlipVertical,
+            Symmetry::FlipDiagonal,
+            Symmetry::FlipAntiDiagonal,
+        ] {
+            assert_eq!(symmetry.apply(&game.transform(symmetry)), board);
+        }
+        assert_eq!(game.transform(Symmetry::FlipHorizontal)[2], Cell::X);
+        assert_eq!(game.transform(Symmetry::FlipDiagonal)[3], Cell::O);
+    }
+
+    #[test]
+    fn symmetric_positions_share_canonical_form() {
+        // X in a corner with O on an adjacent edge, mirrored across the diagonal.
+        let mut a = GameEngine::new();
+        a.simulate(&[0, 1, 8]);
+        let mut b = GameEngine::new();
+        b.simulate(&[0, 3, 8]);
+        assert_eq!(a.canonical_form(), b.canonical_form());
+
+        // O in the center instead is a different position.
+        let mut c = GameEngine::new();
+        c.simulate(&[0, 4, 8]);
+        assert_ne!(a.canonical_form(), c.canonical_form());
+
+        let canonical = a.canonical_form();
+        assert!(Symmetry::ALL.iter().all(|&s| canonical <= a.transform(s)));
+    }
+
+    #[test]
+    fn result_summarizes_finished_games() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 4, 5]);
+        assert_eq!(game.winning_line(), None);
+        assert_eq!(game.result(), None);
+
+        game.make_move(8).unwrap(); // X wins on the diagonal
+        let result = game.result().unwrap();
+        assert_eq!(result.state, GameState::Win(Player::X));
+        assert_eq!(result.winning_line, Some(vec![0, 4, 8]));
+        assert_eq!(result.moves, vec![0, 3, 4, 5, 8]);
+        assert_eq!(result.move_count, 5);
+
+        let mut tie = GameEngine::new();
+        tie.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        let result = tie.result().unwrap();
+        assert_eq!(result.state, GameState::Tie);
+        assert_eq!(result.winning_line, None);
+        assert_eq!(result.move_count, 9);
+    }
+
+    #[test]
+    fn lines_through_each_cell() {
+        let counts: Vec<usize> = (0..9).map(|i| GameEngine::lines_through(i).len()).collect();
+        assert_eq!(counts, vec![3, 2, 3, 2, 4, 2, 3, 2, 3]);
+        assert_eq!(
+            GameEngine::lines_through(0),
+            vec![[0, 1, 2], [0, 3, 6], [0, 4, 8]]
+        );
+        assert!(GameEngine::lines_through(9).is_empty());
+    }
+
+    #[test]
+    fn open_line_score_weights_marks() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+        // X: the top row holds 2 marks, the left column 1, and the bottom row
+        // and right column none; O blocks every line through the center.
+        assert_eq!(game.open_line_score(Player::X), 100 + 10 + 1 + 1);
+        // O: the middle row and anti-diagonal hold 1 mark; the bottom row and
+        // right column none.
+        assert_eq!(game.open_line_score(Player::O), 10 + 10 + 1 + 1);
+    }
+
+    #[test]
+    fn depth_limited_search() {
+        // With enough depth to reach the end, play matches the full search.
+        let mut game = GameEngine::new();
+        while let Some(best) = game.get_best_move() {
+            assert_eq!(game.get_best_move_limited(9), Some(best));
+            game.make_move(best).unwrap();
+        }
+
+        // Even a one-move search takes an immediate win.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4]);
+        assert_eq!(game.get_best_move_limited(1), Some(2));
+        assert_eq!(game.get_best_move_limited(0), Some(2));
+
+        game.ai_enabled = false;
+        assert_eq!(game.get_best_move_limited(3), None);
+    }
+
+    #[test]
+    fn undo_to_jumps_through_history() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8, 2, 6]);
+        let finished = *game.get_board();
+
+        game.undo_to(0).unwrap();
+        assert_eq!(game.get_board(), &[Cell::Empty; 9]);
+        assert_eq!(game.current_player, Player::X);
+
+        game.undo_to(2).unwrap();
+        assert_eq!(game.get_history(), &[4, 0]);
+        assert_eq!(game.current_player, Player::X);
+
+        game.undo_to(5).unwrap();
+        assert_eq!(game.get_board(), &finished);
+
+        assert_eq!(game.undo_to(6), Err(MoveError::NothingToRedo));
+        assert_eq!(game.get_board(), &finished);
+    }
+
+    #[test]
+    fn current_player_can_win() {
+        let mut game = GameEngine::new();
+        assert!(!game.can_current_player_win());
+
+        // X sets up a fork; O is to move and can't win.
+        game.simulate(&[0, 1, 4, 8, 6]);
+        assert!(!game.can_current_player_win());
+        game.make_move(3).unwrap(); // O blocks one threat
+        assert!(game.can_current_player_win());
+
+        game.make_move(2).unwrap(); // X wins
+        assert!(!game.can_current_player_win());
+    }
+
+    #[test]
+    fn current_player_can_hold_draw() {
+        let mut game = GameEngine::new();
+        assert!(game.can_hold_draw());
+
+        // O answers the center with an edge: X wins with best play, so O is
+        // lost without facing a fork yet.
+        game.simulate(&[4, 1]);
+        assert!(game.can_hold_draw());
+        game.make_move(game.get_best_move().unwrap()).unwrap();
+        assert!(!game.in_losing_fork());
+        assert!(!game.can_hold_draw());
+
+        // Finished games: a tie is held, a loss isn't.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        assert!(game.can_hold_draw());
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4, 2]);
+        assert!(!game.can_hold_draw());
+
+        // A resignation isn't a draw whoever resigned, while an agreed draw is.
+        let mut game = GameEngine::new();
+        game.resign(Player::X);
+        assert!(!game.can_hold_draw());
+        let mut game = GameEngine::new();
+        game.resign(Player::O);
+        assert!(!game.can_hold_draw());
+        let mut game = GameEngine::new();
+        game.agree_draw();
+        assert!(game.can_hold_draw());
+    }
+
+    #[test]
+    fn replay_yields_each_position() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8]);
+        game.undo_move().unwrap();
+
+        let (x, o, e) = (Cell::X, Cell::O, Cell::Empty);
+        let frames: Vec<[Cell; 9]> = game.replay().collect();
+        assert_eq!(
+            frames,
+            vec![[e, e, e, e, x, e, e, e, e], [o, e, e, e, x, e, e, e, e]]
+        );
+
+        // Marks loaded with the board are part of every frame.
+        let mut loaded = GameEngine::from_board([x, o, e, e, e, e, e, e, e]).unwrap();
+        loaded.make_move(4).unwrap();
+        let frames: Vec<[Cell; 9]> = loaded.replay().collect();
+        assert_eq!(frames, vec![[x, o, e, e, x, e, e, e, e]]);
+    }
+
+    #[test]
+    fn display_uses_custom_symbols() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 8]);
+        assert_eq!(game.to_string(), "X..\n.O.\n..X");
+
+        game.set_symbols('❌', '⭕', '·');
+        assert_eq!(game.to_string(), "❌··\n·⭕·\n··❌");
+    }
+
+    #[test]
+    fn detects_losing_forks() {
+        // A single threat can be blocked.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+        assert!(!game.in_losing_fork());
+
+        // Two threats can't.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 4, 8, 6]);
+        assert!(game.in_losing_fork());
+
+        // Facing two threats with a win of its own, O isn't lost.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1, 5, 6]);
+        assert_eq!(game.winning_moves_for(Player::X), vec![2, 3]);
+        assert!(!game.in_losing_fork());
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        let mut game = GameEngine::with_starting_player(Player::O, true);
+        game.simulate(&[4, 0, 8, 2, 1, 6]);
+        let notation = game.to_notation();
+        assert_eq!(notation, "Ob2 Xa1 Oc3 Xc1 Ob1 Xa3");
+
+        let restored = GameEngine::from_notation(&notation).unwrap();
+        assert_eq!(restored.get_board(), game.get_board());
+        assert_eq!(restored.get_history(), game.get_history());
+        assert_eq!(restored.current_player, game.current_player);
+
+        // Players follow from the opener, after a win or a resignation too.
+        let mut game = GameEngine::with_starting_player(Player::O, false);
+        game.simulate(&[0, 3, 1, 4, 2]);
+        assert_eq!(game.to_notation(), "Oa1 Xa2 Ob1 Xb2 Oc1");
+        let mut game = GameEngine::with_starting_player(Player::O, false);
+        game.simulate(&[4]);
+        game.resign(Player::X);
+        assert_eq!(game.to_notation(), "Ob2");
+
+        assert_eq!(GameEngine::from_notation("  ").unwrap().move_count(), 0);
+        assert_eq!(
+            GameEngine::from_notation("Xb2 Od4").err(),
+            Some(NotationError::InvalidMove(1))
+        );
+        assert_eq!(
+            GameEngine::from_notation("Xb2 Xa1").err(),
+            Some(NotationError::WrongPlayer(1))
+        );
+    }
+
+    #[test]
+    fn classifies_cells() {
+        use CellKind::{Center, Corner, Edge};
+        let kinds: Vec<Option<CellKind>> = (0..9).map(GameEngine::cell_kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                Corner, Edge, Corner, Edge, Center, Edge, Corner, Edge, Corner
+            ]
+            .map(Some)
+        );
+        assert_eq!(GameEngine::cell_kind(9), None);
+    }
+
+    #[test]
+    fn handicap_plays_weaker_moves() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+
+        // Fully handicapped, O never blocks the threat at 2.
+        game.handicap = 1.0;
+        let weak = game.get_best_move().unwrap();
+        assert_ne!(weak, 2);
+        assert_eq!(game.get_best_move(), Some(weak));
+
+        // Without a handicap, the AI stays unbeatable.
+        game.handicap = 0.0;
+        assert_eq!(game.get_best_move(), Some(2));
+
+        // A partial handicap is reproducible for a fixed seed.
+        let play = |seed| {
+            let mut game = GameEngine::builder().seed(seed).handicap(0.5).build();
+            game.play_out();
+            *game.get_board()
+        };
+        assert_eq!(play(7), play(7));
+    }
+
+    #[test]
+    fn parses_typed_moves() {
+        // Plain indices.
+        assert_eq!(GameEngine::parse_move("0"), Ok(0));
+        assert_eq!(GameEngine::parse_move(" 8\n"), Ok(8));
+        // Row and column pairs.
+        assert_eq!(GameEngine::parse_move("0,0"), Ok(0));
+        assert_eq!(GameEngine::parse_move("2, 1"), Ok(7));
+        // Chess-like coordinates.
+        assert_eq!(GameEngine::parse_move("a1"), Ok(0));
+        assert_eq!(GameEngine::parse_move("C3"), Ok(8));
+        assert_eq!(GameEngine::parse_move("c1"), Ok(2));
+
+        assert_eq!(
+            GameEngine::parse_move("9"),
+            Err(ParseMoveError::OutOfBounds)
+        );
+        assert_eq!(
+            GameEngine::parse_move("1,3"),
+            Err(ParseMoveError::OutOfBounds)
+        );
+        assert_eq!(
+            GameEngine::parse_move("a4"),
+            Err(ParseMoveError::OutOfBounds)
+        );
+        assert_eq!(
+            GameEngine::parse_move(""),
+            Err(ParseMoveError::InvalidFormat)
+        );
+        assert_eq!(
+            GameEngine::parse_move("x,1"),
+            Err(ParseMoveError::InvalidFormat)
+        );
+        assert_eq!(
+            GameEngine::parse_move("center"),
+            Err(ParseMoveError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn cached_state_matches_full_check() {
+        for seed in 0..200 {
+            let mut game = GameEngine::builder().seed(seed).build();
+            while game.play_random_move().is_some() {
+                assert_eq!(game.check_state(), game.check_board_state(game.board));
+            }
+            while game.undo_move().is_ok() {
+                assert_eq!(game.check_state(), game.check_board_state(game.board));
+            }
+            while game.redo_move().is_ok() {
+                assert_eq!(game.check_state(), game.check_board_state(game.board));
+            }
+        }
+    }
+
+    #[test]
+    fn snapshots_are_independent_and_shareable() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4]);
+        let snapshot = game.snapshot();
+        assert_send_sync(&snapshot);
+
+        game.make_move(2).unwrap(); // X wins
+        assert_eq!(snapshot.state(), GameState::InProgress);
+        assert!(!snapshot.is_over());
+        assert_eq!(snapshot.board()[2], Cell::Empty);
+        assert_eq!(game.snapshot().state(), GameState::Win(Player::X));
+
+        let handle = std::thread::spawn(move || snapshot.current_player());
+        assert_eq!(handle.join().unwrap(), Player::X);
+    }
+
+    #[test]
+    fn scoring_changes_move_choice() {
+        // O can win at once at 6, or block X at 3 and settle for a draw.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 2, 1, 7, 4, 8, 5]);
+        assert_eq!(game.get_best_move(), Some(6));
+
+        game.scoring = ScoringConfig {
+            win: 1,
+            loss: -10,
+            tie: 5,
+        };
+        assert_eq!(game.get_best_move(), Some(3));
+        // The outcome is judged by the standard scores all the same.
+        assert_eq!(game.forced_outcome(), Some(GameState::Win(Player::O)));
+
+        game.make_move(3).unwrap();
+        game.play_out();
+        assert_eq!(game.check_state(), GameState::Tie);
+    }
+
+    #[test]
+    fn exports_moves_with_players() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8, 2]);
+        game.undo_move().unwrap();
+        assert_eq!(
+            game.export_moves(),
+            vec![(Player::X, 4), (Player::O, 0), (Player::X, 8)]
+        );
+    }
+
+    #[test]
+    fn import_moves_round_trips() {
+        let mut game = GameEngine::with_starting_player(Player::O, true);
+        game.simulate(&[4, 0, 8, 2]);
+        let moves = game.export_moves();
+
+        let restored = GameEngine::import_moves(&moves, true).unwrap();
+        assert_eq!(restored.get_board(), game.get_board());
+        assert_eq!(restored.current_player, game.current_player);
+
+        assert_eq!(
+            GameEngine::import_moves(&[], true).unwrap().current_player,
+            Player::X
+        );
+        assert_eq!(
+            GameEngine::import_moves(&[(Player::X, 4), (Player::O, 4)], true).err(),
+            Some((1, MoveError::CellOccupied))
+        );
+    }
+
+    #[test]
+    fn dead_lines_grow_with_the_game() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0]);
+        assert_eq!(game.dead_lines(), vec![[0, 4, 8]]);
+
+        game.simulate(&[8, 2]);
+        assert_eq!(game.dead_lines(), vec![[2, 5, 8], [0, 4, 8], [2, 4, 6]]);
+
+        // Wrap-around and shorter lines count too.
+        let mut game = GameEngine::builder().ai(false).wrap(true).build();
+        game.simulate(&[1, 3]);
+        assert_eq!(game.dead_lines(), vec![[1, 3, 8]]);
+        let mut game = GameEngine::builder().ai(false).win_len(2).build();
+        game.simulate(&[0, 4]);
+        assert_eq!(game.dead_lines(), vec![[0, 4]]);
+    }
+
+    #[test]
+    fn board_copy_outlives_moves() {
+        let mut game = GameEngine::new();
+        let board = game.board_copy();
+        for index in (0..9).filter(|&i| board[i] == Cell::Empty).take(3) {
+            game.make_move(index).unwrap();
+        }
+        assert_eq!(board, [Cell::Empty; 9]);
+        assert_eq!(game.board_copy()[..3], [Cell::X, Cell::O, Cell::X]);
+    }
+
+    #[test]
+    fn counts_searched_nodes() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7]);
+        // O and X fill the last two cells in either order.
+        assert_eq!(game.count_nodes(), (4, 4));
+
+        game.simulate(&[6, 8]);
+        assert_eq!(game.count_nodes(), (0, 0));
+    }
+
+    #[test]
+    fn fork_candidates_for_current_player() {
+        let mut game = GameEngine::with_ai(false);
+        game.simulate(&[0, 4, 8]);
+        // O has no fork to make; X's don't count on O's turn.
+        assert!(game.fork_candidates().is_empty());
+
+        // Blocking O at 6 also gives X threats at 3 and 7.
+        game.make_move(2).unwrap();
+        assert_eq!(game.fork_candidates(), vec![6]);
+    }
+
+    #[test]
+    fn counts_complete_games() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7]);
+        // O plays 6 or 8, and X fills the other cell.
+        assert_eq!(game.count_games_from(), 2);
+
+        game.simulate(&[6]);
+        assert_eq!(game.count_games_from(), 1);
+        game.make_move(8).unwrap();
+        assert_eq!(game.count_games_from(), 1);
+
+        // A resignation ends the game however open the board is.
+        let mut game = GameEngine::new();
+        game.resign(Player::X);
+        assert_eq!(game.count_games_from(), 1);
+    }
+
+    #[test]
+    fn no_moves_after_game_over() {
+        for ai_enabled in [false, true] {
+            let mut game = GameEngine::with_ai(ai_enabled);
+            game.simulate(&[0, 3, 1, 4, 2]); // X wins
+            let board = *game.get_board();
+            assert_eq!(game.make_move(5), Err(MoveError::GameOver));
+            assert_eq!(game.get_board(), &board);
+            assert_eq!(game.get_history().len(), 5);
+        }
+
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]); // Tie
+        assert_eq!(game.assert_in_progress(), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn perspectives_negate_scores() {
+        let positions: [&[usize]; 5] = [&[], &[4], &[0, 4, 1], &[0, 1, 4, 8, 6], &[0, 3, 1, 4, 2]];
+        for moves in positions {
+            let mut game = GameEngine::new();
+            game.simulate(moves);
+            let board = Bitboard::from_cells(game.get_board());
+
+            // Evaluate the position with the same player to move from both sides,
+            // searched to the end and estimated after one move.
+            for depth in [UNLIMITED_DEPTH, 1] {
+                let evaluate = |maximizer| {
+                    let mut search = Search::new(maximizer);
+                    game.minimax_with_pruning(
+                        board,
+                        game.current_player,
+                        depth,
+                        -i32::MAX,
+                        i32::MAX,
+                        &mut search,
+                    )
+                };
+                assert_eq!(evaluate(Player::X), -evaluate(Player::O), "{:?}", moves);
+            }
+        }
+
+        // X is to move, but O's best move is still found: it wins against any defense.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1, 2, 8]);
+        assert_eq!(game.current_player, Player::O);
+        game.current_player = Player::X;
+        let best = game.get_best_move_for(Player::O).unwrap();
+        game.current_player = Player::O;
+        game.make_move(best).unwrap();
+        assert_eq!(game.forced_outcome(), Some(GameState::Win(Player::O)));
+    }
+
+    #[test]
+    fn opening_book_lists_distinct_openings() {
+        let mut game = GameEngine::with_ai(false);
+        let book = game.opening_book();
+        assert_eq!(book, [(0, 0), (1, 0), (4, 0)]);
+
+        // Every other opening is a symmetric copy of one in the book.
+        let scores = game.move_scores();
+        for (index, score) in scores {
+            assert!(book.iter().any(|&(opening, opening_score)| {
+                opening_score == score
+                    && Symmetry::ALL
+                        .iter()
+                        .any(|symmetry| symmetry.map_index(opening) == index)
+            }));
+        }
+
+        // After the first move, the book falls back to the ranked moves.
+        game.simulate(&[0, 4, 1]);
+        let book = game.opening_book();
+        assert_eq!(
+            book.iter().map(|&(index, _)| index).collect::<Vec<_>>(),
+            game.ranked_moves()
+        );
+        assert_eq!(book[0], (2, 0));
+    }
+
+    #[test]
+    fn move_tokens_undo_exactly() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0]);
+        game.undo_move().unwrap();
+        let before = game.snapshot();
+
+        // Making moves discards the undone move; undoing them brings it back.
+        let tokens: Vec<_> = [8, 2, 6, 1, 7]
+            .iter()
+            .map(|&index| game.make_move_tok(index).unwrap())
+            .collect();
+        assert_eq!(game.check_state(), GameState::Win(Player::O));
+        assert_eq!(game.make_move_tok(3), Err(MoveError::GameOver));
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+
+        for tok in tokens.into_iter().rev() {
+            game.undo_tok(tok);
+        }
+        assert_eq!(game.snapshot(), before);
+        assert_eq!(game.get_history(), &[4]);
+        assert_eq!(game.redo_move(), Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "LIFO")]
+    fn move_tokens_undo_in_order() {
+        let mut game = GameEngine::new();
+        let first = game.make_move_tok(4).unwrap();
+        let _second = game.make_move_tok(0).unwrap();
+        game.undo_tok(first);
+    }
+
+    #[test]
+    fn principal_variation_plays_to_the_end() {
+        // From the empty board, best play fills the board and ties.
+        let mut game = GameEngine::with_ai(false);
+        let line = game.principal_variation();
+        assert_eq!(line.len(), 9);
+        game.simulate(&line);
+        assert_eq!(game.check_state(), GameState::Tie);
+        assert!(game.principal_variation().is_empty());
+
+        // The line starts with the AI's move and follows its replies.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+        let line = game.principal_variation();
+        assert_eq!(line[0], 2);
+        for index in line {
+            assert_eq!(game.get_best_move(), Some(index));
+            game.make_move(index).unwrap();
+        }
+        assert!(game.is_over());
+    }
+
     #[test]
-    fn x_can_win() {
+    fn strategies_play_full_games() {
+        use crate::strategy::FirstAvailable;
+
+        let mut game = GameEngine::with_ai(false);
+        assert_eq!(
+            game.play_out_with(&mut Minimax, &mut Minimax),
+            GameState::Tie
+        );
+
+        // Perfect play never loses to random moves, from either side.
+        for seed in 0..20 {
+            let mut game = GameEngine::new();
+            let result = game.play_out_with(&mut Random::new(seed), &mut Minimax);
+            assert_ne!(result, GameState::Win(Player::X));
+            let mut game = GameEngine::new();
+            let result = game.play_out_with(&mut Minimax, &mut Random::new(seed));
+            assert_ne!(result, GameState::Win(Player::O));
+        }
+
+        // The same seed replays the same game.
+        let mut a = GameEngine::new();
+        a.play_out_with(&mut Random::new(7), &mut Random::new(8));
+        let mut b = GameEngine::new();
+        b.play_out_with(&mut Random::new(7), &mut Random::new(8));
+        assert_eq!(a.get_history(), b.get_history());
+
+        // FirstAvailable fills the board in order, so X completes the left column.
         let mut game = GameEngine::new();
-        game.make_move(0).unwrap(); // X
-        game.make_move(3).unwrap(); // O
-        game.make_move(1).unwrap(); // X
-        game.make_move(4).unwrap(); // O
-        game.make_move(2).unwrap(); // X wins
-        assert_eq!(game.check_state(), GameState::Win(Player::X));
+        let result = game.play_out_with(&mut FirstAvailable, &mut FirstAvailable);
+        assert_eq!(result, GameState::Win(Player::X));
+        assert_eq!(game.get_history(), &[0, 1, 2, 3, 4, 5, 6]);
     }
 
     #[test]
-    fn tie_game() {
+    fn impossible_boards_dont_panic() {
+        let game = GameEngine::new();
+
+        // Both players have completed a line, which no game can reach.
+        let mut board = [Cell::Empty; 9];
+        board[..3].fill(Cell::O);
+        board[6..].fill(Cell::X);
+        assert_eq!(game.check_board_state(board), GameState::Win(Player::O));
+
+        // A full board of a single mark.
+        assert_eq!(
+            game.check_board_state([Cell::X; 9]),
+            GameState::Win(Player::X)
+        );
+        assert_eq!(
+            game.check_board_state([Cell::Empty; 9]),
+            GameState::InProgress
+        );
+    }
+
+    #[test]
+    fn outcome_distribution_is_reproducible() {
+        let game = GameEngine::builder().seed(3).build();
+        let distribution = game.outcome_distribution_vs_random(30);
+        assert_eq!(game.outcome_distribution_vs_random(30), distribution);
+
+        let (wins, draws, losses) = distribution;
+        assert_eq!(losses, 0.0);
+        assert!((wins + draws - 1.0).abs() < 1e-9);
+
+        // Once the game is over, every sample ends the same way.
         let mut game = GameEngine::new();
-        let moves = [0, 1, 2, 4, 3, 5, 7, 6, 8];
-        for &i in &moves {
-            game.make_move(i).unwrap();
-        }
-        assert_eq!(game.check_state(), GameState::Tie);
+        game.simulate(&[0, 3, 1, 4, 2]); // X wins, O to move
+        assert_eq!(game.outcome_distribution_vs_random(10), (0.0, 0.0, 1.0));
+
+        assert_eq!(game.outcome_distribution_vs_random(0), (0.0, 0.0, 0.0));
     }
 
     #[test]
-    fn invalid_move_out_of_bounds() {
+    fn position_keys_are_stable() {
         let mut game = GameEngine::new();
-        assert_eq!(game.make_move(9), Err(MoveError::OutOfBounds));
+        game.simulate(&[4, 0, 8]);
+        // X in 4 and 8, O in 0, O to move.
+        assert_eq!(game.position_key(), 0b1_01_00_00_00_01_00_00_00_10);
+
+        // The same position reached in another order has the same key.
+        let mut other = GameEngine::with_ai(false);
+        other.simulate(&[8, 0, 4]);
+        assert_eq!(other.position_key(), game.position_key());
+
+        // The player to move is part of the key.
+        let mut x_to_move = game.clone();
+        x_to_move.current_player = Player::X;
+        assert_eq!(x_to_move.position_key(), game.position_key() & !(1 << 18));
     }
 
     #[test]
-    fn invalid_move_occupied() {
+    fn ai_move_is_applied() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1]);
+        let board = *game.get_board();
+
+        let index = game.make_ai_move().unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(game.last_move(), Some(index));
+        assert_eq!(game.get_board()[index], Cell::O);
+        assert_eq!(game.current_player, Player::X);
+
+        // Without AI nothing is played.
+        let mut game = GameEngine::with_ai(false);
+        game.simulate(&[0, 3, 1]);
+        assert_eq!(game.make_ai_move(), None);
+        assert_eq!(game.get_board(), &board);
+    }
+
+    #[test]
+    fn safe_moves_avoid_blunders() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.safe_moves(), (0..9).collect::<Vec<_>>());
+
+        // After opposite corners around O's center, a corner lets X fork.
+        game.simulate(&[0, 4, 8]);
+        assert_eq!(game.safe_moves(), vec![1, 3, 5, 7]);
+
+        // Winning is always safe, even with the opponent threatening too.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4]);
+        assert_eq!(game.safe_moves(), vec![2, 5]);
+
+        game.make_move(2).unwrap();
+        assert!(game.safe_moves().is_empty());
+    }
+
+    #[test]
+    fn tie_breaks_shape_choices() {
+        // Every opening draws, so all nine moves are tied.
         let mut game = GameEngine::new();
+        for (tie_break, expected) in [
+            (TieBreak::LowestIndex, 0),
+            (TieBreak::HighestIndex, 8),
+            (TieBreak::PreferCenter, 4),
+            (TieBreak::PreferCorners, 0),
+        ] {
+            game.tie_break = tie_break;
+            assert_eq!(game.get_best_move(), Some(expected), "{:?}", tie_break);
+        }
+
+        // After a corner opening, only the center holds the draw for O, so it is
+        // picked whatever the preference.
         game.make_move(0).unwrap();
-        assert_eq!(game.make_move(0), Err(MoveError::CellOccupied));
+        assert_eq!(game.get_best_moves(), vec![4]);
+        game.tie_break = TieBreak::PreferCorners;
+        assert_eq!(game.get_best_move(), Some(4));
+
+        // With the center taken, PreferCenter falls back to the lowest index.
+        let mut game = GameEngine::builder()
+            .tie_break(TieBreak::PreferCenter)
+            .build();
+        game.simulate(&[4]);
+        assert_eq!(game.get_best_move(), Some(0));
+
+        // Random matches randomize_ties.
+        let random = GameEngine::builder()
+            .seed(5)
+            .tie_break(TieBreak::Random)
+            .build();
+        let randomized = GameEngine::builder().seed(5).randomize_ties(true).build();
+        assert_eq!(random.get_best_move(), randomized.get_best_move());
     }
 
     #[test]
-    fn minimax_ai_blocks_win() {
+    fn ascii_art_snapshot() {
         let mut game = GameEngine::new();
-        game.make_move(0).unwrap(); // X
-        game.make_move(4).unwrap(); // O
-        game.make_move(1).unwrap(); // X
-        // O (AI) should now block X at 2
+        game.simulate(&[0, 4, 8, 2]);
+        let expected = "\
+┌───┬───┬───┐
+│ X │ . │ O │
+├───┼───┼───┤
+│ . │ O │ . │
+├───┼───┼───┤
+│ . │ . │ X │
+└───┴───┴───┘";
+        assert_eq!(game.to_ascii_art(false), expected);
+
+        let expected = "\
+┌───┬───┬───┐
+│ X │ 1 │ O │
+├───┼───┼───┤
+│ 3 │ O │ 5 │
+├───┼───┼───┤
+│ 6 │ 7 │ X │
+└───┴───┴───┘";
+        assert_eq!(game.to_ascii_art(true), expected);
+    }
+
+    #[test]
+    fn finds_immediate_wins() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.immediate_win(), None);
+
+        // X can complete the top row at 2 or the left column at 6.
+        game.simulate(&[0, 4, 1, 8, 3]);
+        assert_eq!(game.immediate_win(), None); // O has no two in a line left open
+        game.make_move(5).unwrap();
+        assert_eq!(game.immediate_win(), Some(2));
+        assert_eq!(game.winning_moves_for(Player::X), vec![2, 6]);
+
+        // Once the game is won, there is nothing left to win.
+        game.make_move(2).unwrap();
+        assert_eq!(game.immediate_win(), None);
+    }
+
+    #[test]
+    fn finds_required_blocks() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.required_block(), None);
+
+        // No threat yet.
+        game.simulate(&[0, 4]);
+        assert_eq!(game.required_block(), None);
+
+        // One threat: O must take 2.
+        game.make_move(1).unwrap();
+        assert_eq!(game.required_block(), Some(2));
+
+        // Two threats: either block loses, and the lowest is reported.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 4, 8, 6]);
+        assert!(game.in_losing_fork());
+        assert_eq!(game.required_block(), Some(2));
+    }
+
+    #[test]
+    fn json_export_is_stable() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4, 2]);
+        assert_eq!(
+            game.to_json(),
+            r#"{"board":["X","X","X","O","O",".",".",".","."],"turn":null,"state":"Win","winner":"X"}"#
+        );
+
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        assert_eq!(
+            game.to_json(),
+            r#"{"board":["X","O","X","X","O","O","O","X","X"],"turn":null,"state":"Tie","winner":null}"#
+        );
+
+        // Blocked cells and resignations have their own encodings.
+        let mut game = GameEngine::builder().ai(false).blocked_cells(&[8]).build();
+        game.simulate(&[4]);
+        game.resign(Player::O);
+        assert_eq!(
+            game.to_json(),
+            r##"{"board":[".",".",".",".","X",".",".",".","#"],"turn":null,"state":"Resigned","winner":"X"}"##
+        );
+    }
+
+    #[test]
+    fn counts_reachable_positions() {
+        // Transpositions merge, so there are far fewer positions than games.
+        let game = GameEngine::new();
+        assert_eq!(game.reachable_state_count(), 5_478);
+        assert!(game.reachable_state_count() < game.count_games_from());
+
+        // One move left: the current position and the finished one.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6]);
+        assert_eq!(game.reachable_state_count(), 2);
+
+        game.make_move(8).unwrap();
+        assert_eq!(game.reachable_state_count(), 1);
+    }
+
+    #[test]
+    fn same_line_compares_move_order() {
+        let mut a = GameEngine::new();
+        a.simulate(&[4, 0, 8, 2]);
+        let mut b = GameEngine::with_ai(false);
+        b.simulate(&[8, 2, 4, 0]);
+        assert_eq!(a.get_board(), b.get_board());
+        assert!(!a.same_line(&b));
+
+        // Settings don't matter, and undone moves aren't part of the line.
+        b.undo_to(0).unwrap();
+        b.simulate(&[4, 0, 8, 2, 6]);
+        b.undo_move().unwrap();
+        assert!(a.same_line(&b));
+
+        // The same moves from different starting players aren't the same game.
+        let mut o_first = GameEngine::with_starting_player(Player::O, true);
+        o_first.simulate(&[4, 0, 8, 2]);
+        assert!(!a.same_line(&o_first));
+    }
+
+    #[test]
+    fn finish_with_ai_plays_both_sides_perfectly() {
+        // From every position after two moves, the game finishes with the
+        // result perfect play predicts.
+        for first in 0..9 {
+            for second in (0..9).filter(|&second| second != first) {
+                let mut game = GameEngine::builder().ai(false).handicap(1.0).build();
+                game.simulate(&[first, second]);
+                let expected = game.forced_outcome().unwrap_or(GameState::Tie);
+                assert_eq!(game.finish_with_ai(), expected);
+                assert!(game.is_over());
+            }
+        }
+
+        // A finished game is left alone.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4, 2]);
+        assert_eq!(game.finish_with_ai(), GameState::Win(Player::X));
+        assert_eq!(game.move_count(), 5);
+    }
+
+    #[test]
+    fn successors_branch_every_legal_move() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8]);
+        let board = *game.get_board();
+
+        let successors: Vec<_> = game.successors().collect();
+        assert_eq!(successors.len(), 6);
+        for (index, next) in &successors {
+            assert_eq!(next.last_move(), Some(*index));
+            assert_eq!(next.current_player, Player::X);
+            assert_eq!(next.move_count(), 4);
+        }
+        assert_eq!(game.get_board(), &board);
+
+        game.simulate(&[2, 6, 3, 5, 7, 1]);
+        assert!(game.is_over());
+        assert_eq!(game.successors().count(), 0);
+    }
+
+    #[test]
+    fn best_losing_move_delays_the_loss() {
+        // O is lost after X's fork setup, and every move but the block at 8
+        // loses at once.
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 1, 0]);
         assert_eq!(game.get_best_move(), Some(2));
+        assert_eq!(game.best_losing_move(), Some(8));
+
+        // After the block, X needs two more moves to win.
+        game.make_move(8).unwrap();
+        assert_eq!(game.principal_variation().len(), 3);
+
+        // Nothing to defend in drawn or won positions.
+        assert_eq!(GameEngine::new().best_losing_move(), None);
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 1]);
+        assert_eq!(game.best_losing_move(), None);
+    }
+
+    #[test]
+    fn toggles_ai_mid_game() {
+        let mut game = GameEngine::with_ai(false);
+        game.simulate(&[0, 4, 1]);
+        assert!(!game.ai_enabled());
+        assert_eq!(game.get_best_move(), None);
+
+        game.set_ai_enabled(true);
+        assert!(game.ai_enabled);
+        assert_eq!(game.get_best_move(), Some(2));
+
+        game.set_ai_enabled(false);
+        assert_eq!(game.make_ai_move(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn timed_search_matches_perfect_play() {
+        use std::time::Duration;
+
+        let mut game = GameEngine::new();
+        while let Some(best) = game.get_best_move() {
+            assert_eq!(
+                game.get_best_move_timed(Duration::from_secs(10)),
+                Some(best)
+            );
+            game.make_move(best).unwrap();
+        }
+        assert_eq!(game.get_best_move_timed(Duration::from_secs(10)), None);
+
+        // Without any time, a one-move search still takes the win.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4]);
+        assert_eq!(game.get_best_move_timed(Duration::ZERO), Some(2));
+    }
+
+    #[test]
+    fn blocked_cells_are_never_played_or_won() {
+        // Blocking the center removes every line through it.
+        let mut game = GameEngine::builder().blocked_cells(&[4]).build();
+        assert!(game.is_empty());
+        assert_eq!(game.make_move(4), Err(MoveError::CellBlocked));
+        assert!(!game.is_valid_move(4));
+        assert_eq!(game.ranked_moves().len(), 8);
+        assert_eq!(game.successors().count(), 8);
+
+        // X holds three corners, but with the diagonals gone only the border
+        // rows and columns can still be won.
+        game.simulate(&[0, 1, 8, 2, 6]);
+        assert_eq!(game.check_state(), GameState::InProgress);
+        assert_eq!(game.immediate_win(), None);
+        assert_eq!(game.required_block(), Some(3));
+
+        // The AI never picks a blocked cell, in self-play or from any position.
+        let mut game = GameEngine::builder().blocked_cells(&[0, 4]).build();
+        while let Some(index) = game.make_ai_move() {
+            assert_ne!(game.get_board()[index], Cell::Blocked);
+        }
+        assert_eq!(game.get_board()[0], Cell::Blocked);
+        assert_eq!(game.get_board()[4], Cell::Blocked);
+
+        // X's opposite corners can't be completed through the hole.
+        let mut board = [Cell::Empty; 9];
+        board[4] = Cell::Blocked;
+        board[0] = Cell::X;
+        board[8] = Cell::X;
+        board[1] = Cell::O;
+        let game = GameEngine::from_board(board).unwrap();
+        assert_eq!(game.check_state(), GameState::InProgress);
+        assert!(game.winning_moves_for(Player::X).is_empty());
+
+        // A board of holes is a tie before it starts.
+        let game = GameEngine::builder()
+            .blocked_cells(&[0, 1, 2, 3, 4, 5, 6, 7, 8])
+            .build();
+        assert_eq!(game.check_state(), GameState::Tie);
+    }
+
+    #[test]
+    fn winning_paths_ignore_dead_lines() {
+        // X holds 1, 3, 4 and 8, which touch every line, while O's marks at
+        // 0, 2, 5 and 6 leave the middle column open to X.
+        let mut board = [Cell::Empty; 9];
+        for index in [1, 3, 4, 8] {
+            board[index] = Cell::X;
+        }
+        for index in [0, 2, 5, 6] {
+            board[index] = Cell::O;
+        }
+        let game = GameEngine::from_board(board).unwrap();
+        assert!(game.has_winning_path(Player::X));
+        assert!(!game.has_winning_path(Player::O));
+
+        // Blocking the last open line leaves X without a path either.
+        board[7] = Cell::Blocked;
+        let game = GameEngine::from_board(board).unwrap();
+        assert!(!game.has_winning_path(Player::X));
+    }
+
+    #[test]
+    fn move_legality_matches_make_move() {
+        let mut game = GameEngine::builder().blocked_cells(&[8]).build();
+        game.simulate(&[0, 4, 2]);
+
+        let legality = game.move_legality();
+        for (index, result) in legality.iter().enumerate() {
+            assert_eq!(result.is_ok(), game.is_valid_move(index));
+            assert_eq!(*result, game.clone().make_move(index));
+        }
+        assert_eq!(legality[0], Err(MoveError::CellOccupied));
+        assert_eq!(legality[4], Err(MoveError::CellOccupied));
+        assert_eq!(legality[8], Err(MoveError::CellBlocked));
+        assert_eq!(legality[1], Ok(()));
+
+        // O blocks, X plays 3, O wins down the middle column.
+        game.simulate(&[1, 3, 7]);
+        assert_eq!(game.check_state(), GameState::Win(Player::O));
+        assert!(
+            game.move_legality()
+                .iter()
+                .all(|result| *result == Err(MoveError::GameOver))
+        );
+    }
+
+    #[test]
+    fn all_threats_reports_both_players() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 3, 1, 4]);
+        assert_eq!(game.all_threats(), (vec![2], vec![5]));
+
+        // Once X takes the row the threats no longer matter.
+        game.simulate(&[2]);
+        assert_eq!(game.all_threats(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn failed_notation_loads_leave_the_game_untouched() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8]);
+        game.undo_move().unwrap();
+        let before = game.clone();
+
+        // A mark in an unknown square, a move out of turn and an illegal move
+        // midway through all fail without touching the game.
+        assert_eq!(
+            game.try_load_notation("Xb2 Od4"),
+            Err(NotationError::InvalidMove(1))
+        );
+        assert_eq!(
+            game.try_load_notation("Xb2 Xa1"),
+            Err(NotationError::WrongPlayer(1))
+        );
+        assert_eq!(
+            game.try_load_notation("Xb2 Oa1 Xc3 Oa1 Xc1"),
+            Err(NotationError::IllegalMove(3, MoveError::CellOccupied))
+        );
+        assert_eq!(game.get_board(), before.get_board());
+        assert_eq!(game.get_history(), before.get_history());
+        assert_eq!(game.current_player, before.current_player);
+        assert_eq!(game.redo_move(), Ok(8));
+
+        // A valid transcript replaces the game, undone moves included, but
+        // keeps the engine's settings.
+        let mut game = GameEngine::builder().ai(false).blocked_cells(&[2]).build();
+        game.simulate(&[4]);
+        game.try_load_notation("Oa1 Xb2 Oa2 Xc3 Oa3").unwrap();
+        assert_eq!(game.get_history(), &[0, 4, 3, 8, 6]);
+        assert_eq!(game.check_state(), GameState::Win(Player::O));
+        assert_eq!(game.get_board()[2], Cell::Blocked);
+        assert!(!game.ai_enabled());
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+    }
+
+    #[test]
+    fn forced_moves() {
+        let mut game = GameEngine::new();
+        assert!(!game.is_move_forced());
+
+        // Every reply but the block at 2 loses.
+        game.simulate(&[0, 4, 1]);
+        assert!(game.is_move_forced());
+        assert_eq!(game.get_best_moves(), vec![2]);
+
+        // Only 8 is left to play.
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6]);
+        assert!(game.is_move_forced());
+        game.simulate(&[8]);
+        assert!(game.is_over());
+        assert!(!game.is_move_forced());
+    }
+
+    #[test]
+    fn resignation_and_agreed_draws_end_the_game() {
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8]);
+        game.undo_move().unwrap();
+        game.resign(Player::X);
+
+        assert!(game.is_over());
+        assert_eq!(game.turn(), None);
+        assert_eq!(game.check_state(), GameState::Resigned(Player::X));
+        assert_eq!(game.check_state().winner(), Some(Player::O));
+        assert_eq!(game.check_state().to_string(), "X resigns, O wins");
+        assert_eq!(game.make_move(8), Err(MoveError::GameOver));
+        assert_eq!(game.redo_move(), Err(MoveError::NothingToRedo));
+        assert_eq!(game.winning_line(), None);
+        assert_eq!(game.get_best_move(), None);
+        assert!(
+            game.to_json()
+                .ends_with(r#""state":"Resigned","winner":"O"}"#)
+        );
+
+        // A finished game can't be resigned or drawn afterwards.
+        game.agree_draw();
+        assert_eq!(game.check_state(), GameState::Resigned(Player::X));
+
+        // Undoing a move takes the resignation back.
+        game.undo_move().unwrap();
+        assert_eq!(game.check_state(), GameState::InProgress);
+        game.agree_draw();
+        assert_eq!(game.check_state(), GameState::DrawAgreed);
+        assert_eq!(game.check_state().winner(), None);
+    }
+
+    #[test]
+    fn confidence_follows_the_forced_outcome() {
+        let mut game = GameEngine::new();
+        assert_eq!(game.confidence(), 0.0);
+
+        // X can fork with 6 next, whatever the AI's own scoring.
+        game.simulate(&[0, 1, 4, 8]);
+        game.scoring = ScoringConfig {
+            win: 1,
+            loss: -100,
+            tie: 50,
+        };
+        assert_eq!(game.confidence(), 1.0);
+
+        game.simulate(&[6, 2, 3]);
+        assert_eq!(game.check_state(), GameState::Win(Player::X));
+        assert_eq!(game.confidence(), -1.0);
+
+        let mut game = GameEngine::new();
+        game.resign(Player::O);
+        assert_eq!(game.confidence(), 1.0);
+    }
+
+    #[test]
+    fn diffs_list_changed_cells_both_ways() {
+        let mut before = GameEngine::new();
+        before.simulate(&[4, 0]);
+        let mut after = before.clone();
+        after.simulate(&[8, 2]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![(2, Cell::Empty, Cell::O), (8, Cell::Empty, Cell::X)]
+        );
+        assert_eq!(
+            after.diff(&before),
+            vec![(2, Cell::O, Cell::Empty), (8, Cell::X, Cell::Empty)]
+        );
+        assert!(after.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn completed_lines_lists_every_line() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+        assert!(game.completed_lines(Player::X).is_empty());
+        assert!(game.completed_lines(Player::O).is_empty());
+
+        // A crafted board where X holds the top row and the left column.
+        let mut board = [Cell::O; 9];
+        for index in [0, 1, 2, 3, 6] {
+            board[index] = Cell::X;
+        }
+        let game = GameEngine::from_board(board).unwrap();
+        assert_eq!(game.completed_lines(Player::X), vec![[0, 1, 2], [0, 3, 6]]);
+        assert_eq!(game.winning_line(), Some(&[0, 1, 2][..]));
+        assert!(game.completed_lines(Player::O).is_empty());
+
+        // On a torus, the broken diagonals are winning lines like any other.
+        let mut game = GameEngine::builder().ai(false).wrap(true).build();
+        game.simulate(&[1, 0, 5, 2, 6]);
+        assert_eq!(game.completed_lines(Player::X), vec![[1, 5, 6]]);
+        let mut game = GameEngine::builder().ai(false).win_len(2).build();
+        game.simulate(&[0, 4, 1]);
+        assert_eq!(game.completed_lines(Player::X), vec![[0, 1]]);
+    }
+
+    #[test]
+    fn phases_follow_the_move_count() {
+        let expected = [
+            GamePhase::Opening,
+            GamePhase::Opening,
+            GamePhase::Opening,
+            GamePhase::Midgame,
+            GamePhase::Midgame,
+            GamePhase::Midgame,
+            GamePhase::Midgame,
+            GamePhase::Endgame,
+            GamePhase::Endgame,
+            GamePhase::Endgame,
+        ];
+
+        // A game that fills the board without a winner.
+        let mut game = GameEngine::new();
+        for (moves, &index) in [0, 1, 2, 4, 3, 5, 7, 6, 8].iter().enumerate() {
+            assert_eq!(game.phase(), expected[moves]);
+            game.simulate(&[index]);
+        }
+        assert_eq!(game.phase(), expected[9]);
+    }
+
+    #[test]
+    fn finished_games_become_records() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        let record = game.into_record().unwrap();
+        assert_eq!(record.state(), GameState::Tie);
+        assert_eq!(record.winner(), None);
+        assert_eq!(record.winning_line(), None);
+        assert_eq!(record.moves(), &[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+        assert!(record.board().iter().all(|&cell| cell != Cell::Empty));
+
+        // Undone moves are left out, and resignations have no winning line.
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0, 8]);
+        game.undo_move().unwrap();
+        game.resign(Player::O);
+        let record = game.into_record().unwrap();
+        assert_eq!(record.moves(), &[4, 0]);
+        assert_eq!(record.winner(), Some(Player::X));
+        assert_eq!(record.winning_line(), None);
+
+        // ...so the record matches one of the same game played without them.
+        let mut game = GameEngine::new();
+        game.simulate(&[4, 0]);
+        game.resign(Player::O);
+        assert_eq!(game.into_record(), Some(record));
+
+        let mut game = GameEngine::new();
+        game.simulate(&[4]);
+        assert_eq!(game.into_record(), None);
+    }
+
+    #[test]
+    fn move_ordering_prunes_the_search() {
+        // Searching wins, blocks and strong cells first solves the empty
+        // board in well under 1,500 positions, with or without the
+        // transposition table; in index order it took several thousand.
+        let (with_pruning, _) = GameEngine::new().count_nodes();
+        assert!(with_pruning < 1_500, "searched {} positions", with_pruning);
+    }
+
+    #[test]
+    fn drawn_out_games_leave_only_filler() {
+        // With the center blocked, the four border lines are dead after four
+        // moves, though four cells are still empty.
+        let mut game = GameEngine::builder().blocked_cells(&[4]).build();
+        assert!(!game.is_drawn_out());
+        game.simulate(&[0, 2, 8]);
+        assert!(!game.is_drawn_out());
+        game.simulate(&[6]);
+        assert!(game.is_drawn_out());
+        assert_eq!(game.ranked_moves().len(), 4);
+        assert_eq!(game.forced_outcome(), Some(GameState::Tie));
+
+        // Playing out the filler ends in a tie, which is no longer "drawn out".
+        game.simulate(&[1, 3, 5, 7]);
+        assert_eq!(game.check_state(), GameState::Tie);
+        assert!(!game.is_drawn_out());
+    }
+
+    #[test]
+    fn worst_moves_mirror_best_moves() {
+        let mut game = GameEngine::new();
+        game.simulate(&[0, 4, 1]);
+        let scores = game.move_scores();
+        let worst = game.get_worst_move().unwrap();
+        let lowest = scores.iter().map(|&(_, score)| score).min().unwrap();
+        assert!(scores.contains(&(worst, lowest)));
+        assert_ne!(worst, 2);
+
+        // The best AI always beats the worst one, whoever starts.
+        for starter in [Player::X, Player::O] {
+            let mut game = GameEngine::with_starting_player(starter, true);
+            let best = starter.opponent();
+            while !game.is_over() {
+                let index = if game.current_player == best {
+                    game.get_best_move()
+                } else {
+                    game.get_worst_move()
+                };
+                game.make_move(index.unwrap()).unwrap();
+            }
+            assert_eq!(game.check_state(), GameState::Win(best));
+        }
+
+        assert_eq!(GameEngine::with_ai(false).get_worst_move(), None);
+    }
+
+    #[test]
+    fn wrap_around_lines_win_on_a_torus() {
+        let mut standard = GameEngine::new();
+        let mut torus = GameEngine::builder().wrap(true).build();
+        assert!(!standard.wraps());
+        assert!(torus.wraps());
+
+        // X's 1 and 5 only make a threat when the diagonal wraps around.
+        for game in [&mut standard, &mut torus] {
+            game.simulate(&[1, 0, 5, 3]);
+        }
+        assert_eq!(standard.immediate_win(), None);
+        assert_eq!(torus.immediate_win(), Some(6));
+        assert_eq!(torus.get_best_move(), Some(6));
+
+        standard.simulate(&[6]);
+        torus.simulate(&[6]);
+        assert_eq!(standard.check_state(), GameState::InProgress);
+        assert_eq!(torus.check_state(), GameState::Win(Player::X));
+        assert_eq!(torus.winning_line(), Some(&[1, 5, 6][..]));
+
+        // The extra lines make the opening a forced win for X.
+        let torus = GameEngine::builder().wrap(true).build();
+        assert_eq!(GameEngine::new().forced_outcome(), None);
+        assert_eq!(torus.forced_outcome(), Some(GameState::Win(Player::X)));
+    }
+
+    #[test]
+    fn turn_parity_follows_mark_counts() {
+        let (x, o, e) = (Cell::X, Cell::O, Cell::Empty);
+        let cases = [
+            ([e, e, e, e, e, e, e, e, e], Some(Player::X)),
+            ([x, e, e, e, e, e, e, e, e], Some(Player::O)),
+            ([x, o, e, e, e, e, e, e, e], Some(Player::X)),
+            // O can't be ahead, nor X two marks ahead.
+            ([o, e, e, e, e, e, e, e, e], None),
+            ([x, x, o, e, e, e, e, e, e], Some(Player::O)),
+            ([x, x, e, e, e, e, e, e, e], None),
+        ];
+        for (board, to_move) in cases {
+            for player in [Player::X, Player::O] {
+                assert_eq!(
+                    GameEngine::turn_is_consistent(&board, player),
+                    to_move == Some(player),
+                    "{:?} to move on {:?}",
+                    player,
+                    board
+                );
+            }
+            assert_eq!(
+                GameEngine::from_board(board)
+                    .ok()
+                    .map(|game| game.current_player),
+                to_move
+            );
+        }
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        // Every position of a batch of random games survives the round trip.
+        for seed in 0..50 {
+            let mut strategy = Random::new(seed);
+            let first = if seed % 2 == 0 { Player::X } else { Player::O };
+            let mut game = GameEngine::with_starting_player(first, true);
+            loop {
+                let decoded = GameEngine::decode(game.encode(), game.current_player).unwrap();
+                assert_eq!(decoded.get_board(), game.get_board());
+                assert_eq!(decoded.current_player, game.current_player);
+                assert_eq!(decoded.check_state(), game.check_state());
+                if game.is_over() {
+                    break;
+                }
+                let index = strategy.choose(&game);
+                game.simulate(&[index]);
+            }
+        }
+
+        // Decoding is the exact inverse of encoding: no two encodings decode
+        // to the same board.
+        for bits in 0..1 << 18 {
+            for player in [Player::X, Player::O] {
+                if let Ok(game) = GameEngine::decode(bits, player) {
+                    assert_eq!(game.encode(), bits);
+                }
+            }
+        }
+        assert_eq!(
+            GameEngine::decode(1 << 18, Player::X).err(),
+            Some(BoardError::InvalidEncoding(1 << 18))
+        );
+
+        // A board O opened decodes with X to move, but not with O.
+        let mut game = GameEngine::with_starting_player(Player::O, false);
+        game.simulate(&[4]);
+        let decoded = GameEngine::decode(game.encode(), Player::X).unwrap();
+        assert_eq!(decoded.get_board(), game.get_board());
+        assert_eq!(
+            GameEngine::decode(game.encode(), Player::O).err(),
+            Some(BoardError::WrongTurn(Player::O))
+        );
+        // Two O marks in the top-left cells, and no X.
+        assert_eq!(
+            GameEngine::decode(0b10_10, Player::X).err(),
+            Some(BoardError::InvalidMarkCounts { x: 0, o: 2 })
+        );
     }
 
     #[test]
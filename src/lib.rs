@@ -6,8 +6,11 @@
 //!
 //! ## High-Level Summary
 //!
-//! - Core types: [`Player`], [`Cell`], [`GameState`], [`MoveError`]
+//! - Core types: [`Player`], [`Cell`], [`GameState`], [`MoveError`], [`BoardError`], [`NotationError`], [`Hint`]
 //! - [`GameEngine`] struct to manage game state and moves
+//! - [`GameEngineBuilder`] for configuring new engines
+//! - [`Match`] for playing a series of games with alternating starts
+//! - [`Strategy`] for pitting move-choosing strategies against each other
 //! - Minimax AI: unbeatable computer player with [`GameEngine::get_best_move`]
 //!
 //! ## Example Usage
@@ -17,10 +20,7 @@
 //!
 //! let mut game = GameEngine::new();
 //! // X plays at 0, O at 4, X at 1, O at 5, X at 2 (X wins)
-//! let moves = [0, 4, 1, 5, 2];
-//! for m in moves {
-//!     game.make_move(m).unwrap();
-//! }
+//! game.simulate_moves(&[0, 4, 1, 5, 2]).unwrap();
 //! assert_eq!(game.check_state(), GameState::Win(Player::X));
 //! ```
 //!
@@ -40,15 +40,50 @@
 //!
 //! The engine is detached from I/O and UI, making it easy to use in CLI, GUI, or web applications.
 //!
+//! ## Features
+//!
+//! - `std` *(default)*: Uses the standard library. Enables `alloc` and lets the AI
+//!   memoize positions in a transposition table.
+//! - `alloc`: Enables APIs returning heap-allocated values, such as
+//!   [`GameEngine::winning_moves_for`].
+//! - `wasm`: Enables `std` and adds a `Game` wrapper exporting the engine to
+//!   JavaScript through `wasm-bindgen`, the crate's only (optional) dependency.
+//!
+//! Without default features the crate is `no_std`. The board is a fixed-size
+//! array, so the core engine, including the AI, needs no heap.
+//!
 //! ## License
 //!
 //! MIT
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod bitboard;
+mod builder;
 mod game_engine;
+mod rng;
+mod series;
+mod strategy;
+mod transposition;
 mod types;
+#[cfg(feature = "wasm")]
+mod wasm;
 
-pub use game_engine::GameEngine;
-pub use types::{Cell, GameState, MoveError, Player};
+pub use builder::GameEngineBuilder;
+pub use game_engine::{GameEngine, WINNING_LINES};
+pub use series::Match;
+pub use strategy::{FirstAvailable, Minimax, Random, Strategy};
+#[cfg(feature = "alloc")]
+pub use types::GameResult;
+pub use types::{
+    BoardError, Cell, CellKind, GamePhase, GameRecord, GameSnapshot, GameState, Hint, HintCategory,
+    MoveError, MoveToken, NotationError, ParseMoveError, Player, ScoringConfig, Symmetry, TieBreak,
+};
+#[cfg(feature = "wasm")]
+pub use wasm::Game;
 
 #[cfg(test)]
 mod tests {
@@ -109,6 +144,16 @@ mod tests {
         assert_eq!(board[0], Cell::X);
     }
 
+    #[test]
+    fn states_and_players_display() {
+        assert_eq!(Player::X.to_string(), "X");
+        assert_eq!(Player::O.to_string(), "O");
+        assert_eq!(GameState::Win(Player::X).to_string(), "X wins");
+        assert_eq!(GameState::Win(Player::O).to_string(), "O wins");
+        assert_eq!(GameState::Tie.to_string(), "Draw");
+        assert_eq!(GameState::InProgress.to_string(), "In progress");
+    }
+
     #[test]
     fn ai_blocks_win() {
         let mut game = GameEngine::new();
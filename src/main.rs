@@ -1,5 +1,5 @@
 use std::io;
-use xo_core::{GameEngine, GameState, MoveError, Player};
+use xo_core::{GameEngine, MoveError, Player};
 
 // --- Main Function to Demonstrate Usage ---
 // This main function is provided to show how to use the GameEngine.
@@ -43,8 +43,7 @@ fn main() {
 
     // If player chose O, AI (X) should move first
     if mode == "1" && player_choice == "2" {
-        let best_move = game.get_best_move().unwrap();
-        game.make_move(best_move).unwrap();
+        game.make_ai_move();
     }
 
     // Game loop
@@ -65,13 +64,7 @@ fn main() {
     println!("--- Final Board ---");
     print_board(&game);
     println!("--- Game Over! ---");
-
-    match game.check_state() {
-        GameState::Win(Player::X) => println!("Player X wins!"),
-        GameState::Win(Player::O) => println!("Player O wins!"),
-        GameState::Tie => println!("It's a tie!"),
-        _ => unreachable!(),
-    }
+    println!("{}!", game.check_state());
 }
 
 fn single_player_turn(game: &mut GameEngine, player_choice: &str) {
@@ -84,15 +77,18 @@ fn single_player_turn(game: &mut GameEngine, player_choice: &str) {
     if game.current_player == human_player {
         loop {
             let mut input = String::new();
-            println!("Your turn ({:#?}), enter move 0-8:", human_player);
+            println!(
+                "Your turn ({}), enter move 0-8, row,col or a1-c3:",
+                human_player
+            );
             io::stdin()
                 .read_line(&mut input)
                 .expect("Failed to read line");
 
-            let index: usize = match input.trim().parse() {
-                Ok(num) => num,
-                Err(_) => {
-                    println!("Invalid input! Please enter a number from 0 to 8.");
+            let index = match GameEngine::parse_move(&input) {
+                Ok(index) => index,
+                Err(err) => {
+                    println!("Invalid input ({})! Try again.", err);
                     continue;
                 }
             };
@@ -101,27 +97,30 @@ fn single_player_turn(game: &mut GameEngine, player_choice: &str) {
                 Ok(()) => break,
                 Err(MoveError::OutOfBounds) => println!("Invalid index! Must be 0-8."),
                 Err(MoveError::CellOccupied) => println!("Cell already taken! Try another."),
+                Err(err) => println!("Invalid move ({:?})! Try another.", err),
             }
         }
     } else {
         println!("AI is thinking...");
-        let best_move = game.get_best_move().unwrap();
-        game.make_move(best_move).unwrap();
+        game.make_ai_move();
     }
 }
 
 fn two_player_turn(game: &mut GameEngine) {
     loop {
         let mut input = String::new();
-        println!("Player {:?}, enter your move (0-8):", game.current_player);
+        println!(
+            "Player {}, enter your move (0-8, row,col or a1-c3):",
+            game.current_player
+        );
         io::stdin()
             .read_line(&mut input)
             .expect("Failed to read line");
 
-        let index: usize = match input.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Invalid input! Please enter a number from 0 to 8.");
+        let index = match GameEngine::parse_move(&input) {
+            Ok(index) => index,
+            Err(err) => {
+                println!("Invalid input ({})! Try again.", err);
                 continue;
             }
         };
@@ -130,22 +129,12 @@ fn two_player_turn(game: &mut GameEngine) {
             Ok(()) => break,
             Err(MoveError::OutOfBounds) => println!("Invalid index! Must be 0-8."),
             Err(MoveError::CellOccupied) => println!("Cell already taken! Try another."),
+            Err(err) => println!("Invalid move ({:?})! Try another.", err),
         }
     }
 }
 
 // Print board helper
 fn print_board(game: &GameEngine) {
-    let board = game.get_board();
-    for i in 0..3 {
-        println!(
-            " {} | {} | {} ",
-            board[i * 3],
-            board[i * 3 + 1],
-            board[i * 3 + 2]
-        );
-        if i < 2 {
-            println!("---|---|---");
-        }
-    }
+    println!("{}", game.to_ascii_art(true));
 }
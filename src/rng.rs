@@ -0,0 +1,70 @@
+/// A small, seedable pseudo-random number generator (SplitMix64).
+///
+/// It is not cryptographically secure, but it is fast, needs no dependencies
+/// or standard library, and produces the same sequence for the same seed on
+/// every platform, which keeps the engine's random choices reproducible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudo-random number in `0..bound`. `bound` must not be zero.
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// Returns `true` with probability `p`, which should be in `0.0..=1.0`.
+    pub(crate) fn chance(&mut self, p: f32) -> bool {
+        // The top 24 bits give a uniform value in `0.0..1.0` that fits an f32 exactly.
+        ((self.next_u64() >> 40) as f32 / (1u32 << 24) as f32) < p
+    }
+
+    /// Returns the index of a pseudo-random set bit of `mask`, or `None` if it is empty.
+    pub(crate) fn pick(&mut self, mask: u16) -> Option<usize> {
+        if mask == 0 {
+            return None;
+        }
+        crate::bitboard::indices(mask).nth(self.below(mask.count_ones() as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn picks_only_set_bits() {
+        let mut rng = Rng::new(7);
+        assert_eq!(rng.pick(0), None);
+        for _ in 0..100 {
+            let index = rng.pick(0b1_0001_0100).unwrap();
+            assert!([2, 4, 8].contains(&index));
+            assert!(rng.below(3) < 3);
+        }
+    }
+}
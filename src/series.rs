@@ -0,0 +1,131 @@
+use crate::game_engine::GameEngine;
+use crate::types::{GameState, Player};
+
+/// Tracks a series of games, such as a best-of-N match.
+///
+/// Each call to [`Match::next_game`] returns a fresh [`GameEngine`], with the
+/// starting player alternating between rounds: X starts the first game, O the
+/// second, and so on. Finished games are tallied with [`Match::record`].
+///
+/// # Example
+/// ```
+/// use xo_core::{GameState, Match, Player};
+///
+/// let mut series = Match::new(false);
+///
+/// let mut game = series.next_game();
+/// assert_eq!(game.current_player, Player::X);
+/// game.simulate_moves(&[0, 3, 1, 4, 2]).unwrap();
+/// series.record(game.check_state());
+///
+/// let game = series.next_game();
+/// assert_eq!(game.current_player, Player::O);
+///
+/// assert_eq!(series.standings(), (1, 0, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Match {
+    /// Whether the AI is enabled in the games of the match.
+    pub ai_enabled: bool,
+    x_wins: usize,
+    o_wins: usize,
+    ties: usize,
+    /// The player who starts the next game.
+    next_starter: Player,
+}
+
+impl Default for Match {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl Match {
+    /// Creates a match with no games played, whose first game X starts.
+    ///
+    /// # Parameters
+    /// - `ai_enabled`: Whether the AI is enabled in the games of the match.
+    pub fn new(ai_enabled: bool) -> Self {
+        Self {
+            ai_enabled,
+            x_wins: 0,
+            o_wins: 0,
+            ties: 0,
+            next_starter: Player::X,
+        }
+    }
+
+    /// Returns a fresh game for the next round and passes the first move to
+    /// the other player for the round after.
+    pub fn next_game(&mut self) -> GameEngine {
+        let starter = self.next_starter;
+        self.next_starter = starter.opponent();
+        GameEngine::with_starting_player(starter, self.ai_enabled)
+    }
+
+    /// Records the outcome of a finished game.
+    ///
+    /// A resignation counts as a win for the opponent, and an agreed draw as
+    /// a tie. `GameState::InProgress` is ignored, as the game has no result yet.
+    pub fn record(&mut self, result: GameState) {
+        match result {
+            GameState::Win(Player::X) | GameState::Resigned(Player::O) => self.x_wins += 1,
+            GameState::Win(Player::O) | GameState::Resigned(Player::X) => self.o_wins += 1,
+            GameState::Tie | GameState::DrawAgreed => self.ties += 1,
+            GameState::InProgress => {}
+        }
+    }
+
+    /// Returns the number of games won by X, won by O and tied, in that order.
+    pub fn standings(&self) -> (usize, usize, usize) {
+        (self.x_wins, self.o_wins, self.ties)
+    }
+
+    /// Returns the number of games recorded so far.
+    pub fn games_played(&self) -> usize {
+        self.x_wins + self.o_wins + self.ties
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn three_scripted_games() {
+        let mut series = Match::new(false);
+
+        // Game 1: X starts and wins the top row.
+        let mut game = series.next_game();
+        assert_eq!(game.current_player, Player::X);
+        game.simulate_moves(&[0, 3, 1, 4, 2]).unwrap();
+        series.record(game.check_state());
+
+        // Game 2: O starts and wins the middle column.
+        let mut game = series.next_game();
+        assert_eq!(game.current_player, Player::O);
+        game.simulate_moves(&[1, 0, 4, 2, 7]).unwrap();
+        series.record(game.check_state());
+
+        // Game 3: X starts again and the game is tied.
+        let mut game = series.next_game();
+        assert_eq!(game.current_player, Player::X);
+        game.simulate_moves(&[0, 1, 2, 4, 3, 5, 7, 6, 8]).unwrap();
+        series.record(game.check_state());
+
+        // Unfinished games don't count.
+        series.record(GameState::InProgress);
+
+        assert_eq!(series.standings(), (1, 1, 1));
+        assert_eq!(series.games_played(), 3);
+        assert_eq!(series.next_game().current_player, Player::O);
+    }
+
+    #[test]
+    fn resignations_and_agreed_draws_are_tallied() {
+        let mut series = Match::new(false);
+        series.record(GameState::Resigned(Player::X));
+        series.record(GameState::DrawAgreed);
+        assert_eq!(series.standings(), (0, 1, 1));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::bitboard::{self, Bitboard};
+use crate::game_engine::{DEFAULT_SEED, GameEngine};
+use crate::rng::Rng;
+
+/// A way of choosing moves, for pitting players against each other with
+/// [`GameEngine::play_out_with`].
+///
+/// # Example
+/// ```
+/// use xo_core::{Cell, GameEngine, Strategy};
+///
+/// /// Takes the center whenever it can, and the first free cell otherwise.
+/// struct CenterFirst;
+///
+/// impl Strategy for CenterFirst {
+///     fn choose(&mut self, game: &GameEngine) -> usize {
+///         if game.is_valid_move(4) {
+///             4
+///         } else {
+///             game.cells().find(|&(_, cell)| cell == Cell::Empty).unwrap().0
+///         }
+///     }
+/// }
+/// ```
+pub trait Strategy {
+    /// Chooses a move for the current player of `game`, which is in progress.
+    ///
+    /// The move must be legal: an empty cell index in `0..9`.
+    fn choose(&mut self, game: &GameEngine) -> usize;
+}
+
+/// Plays perfectly, choosing the move [`GameEngine::get_best_move_for`] finds
+/// for the current player, whether or not the engine's AI is enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn choose(&mut self, game: &GameEngine) -> usize {
+        game.get_best_move_for(game.current_player)
+            .expect("strategies are only asked to move in games in progress")
+    }
+}
+
+/// Chooses a uniformly random legal move.
+///
+/// The moves are drawn from a pseudo-random generator, so a `Random` created
+/// with the same seed makes the same choices.
+#[derive(Debug, Clone)]
+pub struct Random {
+    rng: Rng,
+}
+
+impl Random {
+    /// Creates a random strategy whose choices are derived from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl Strategy for Random {
+    fn choose(&mut self, game: &GameEngine) -> usize {
+        let empty = Bitboard::from_cells(game.get_board()).empty();
+        self.rng
+            .pick(empty)
+            .expect("strategies are only asked to move in games in progress")
+    }
+}
+
+/// Chooses the empty cell with the lowest index, a predictable baseline.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstAvailable;
+
+impl Strategy for FirstAvailable {
+    fn choose(&mut self, game: &GameEngine) -> usize {
+        let empty = Bitboard::from_cells(game.get_board()).empty();
+        bitboard::indices(empty)
+            .next()
+            .expect("strategies are only asked to move in games in progress")
+    }
+}
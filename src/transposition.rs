@@ -0,0 +1,89 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::bitboard::Bitboard;
+use crate::types::Player;
+
+/// How a stored score relates to the true minimax value of a position.
+///
+/// Alpha-beta pruning stops searching a position as soon as its score falls
+/// outside the `alpha..beta` window, so a stored score is only exact when the
+/// search completed inside that window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Bound {
+    /// The score is the exact minimax value.
+    Exact,
+    /// The true value is at least the score.
+    Lower,
+    /// The true value is at most the score.
+    Upper,
+}
+
+/// A stored minimax evaluation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) score: i32,
+    pub(crate) bound: Bound,
+}
+
+/// Memoizes minimax evaluations of positions reached through different move orders.
+///
+/// Positions are keyed by the board and the player to move. A table is only
+/// valid for a single search, since scores depend on the searching player.
+///
+/// Without the `std` feature the table stores nothing, and every position is
+/// searched from scratch.
+#[derive(Debug, Default)]
+pub(crate) struct TranspositionTable {
+    #[cfg(feature = "std")]
+    entries: HashMap<u32, Entry>,
+}
+
+impl TranspositionTable {
+    /// Creates an empty table.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up the stored evaluation of `board` with `player` to move.
+    #[cfg(feature = "std")]
+    pub(crate) fn get(&self, board: &Bitboard, player: Player) -> Option<Entry> {
+        self.entries.get(&board.key(player)).copied()
+    }
+
+    /// Looks up the stored evaluation of `board` with `player` to move.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn get(&self, _board: &Bitboard, _player: Player) -> Option<Entry> {
+        None
+    }
+
+    /// Stores the evaluation of `board` with `player` to move.
+    #[cfg(feature = "std")]
+    pub(crate) fn insert(&mut self, board: &Bitboard, player: Player, entry: Entry) {
+        self.entries.insert(board.key(player), entry);
+    }
+
+    /// Stores the evaluation of `board` with `player` to move.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn insert(&mut self, _board: &Bitboard, _player: Player, _entry: Entry) {}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::types::Cell;
+
+    #[test]
+    fn keys_include_player_to_move() {
+        let board = Bitboard::from_cells(&[Cell::Empty; 9]).with_move(4, Player::X);
+        let entry = Entry {
+            score: 0,
+            bound: Bound::Exact,
+        };
+
+        let mut table = TranspositionTable::new();
+        table.insert(&board, Player::O, entry);
+        assert_eq!(table.get(&board, Player::O), Some(entry));
+        assert_eq!(table.get(&board, Player::X), None);
+    }
+}
@@ -1,5 +1,9 @@
 // --- Data Structures for the Game Engine ---
 
+use crate::bitboard::SYMMETRIES;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Represents the two possible players in the game.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Player {
@@ -17,17 +21,34 @@ impl Player {
     }
 }
 
+// The core library's `fmt` module is imported for printing and formatting.
+use core::fmt;
+
+impl fmt::Display for Player {
+    /// Implements the `Display` trait to print a `Player` as its mark.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Player::X => write!(f, "X"),
+            Player::O => write!(f, "O"),
+        }
+    }
+}
+
 /// Represents the state of a single cell on the board.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Cells are ordered `X < O < Empty < Blocked`, which makes boards
+/// comparable, e.g. for [`GameEngine::canonical_form`](crate::GameEngine::canonical_form).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Cell {
     X,
     O,
     Empty,
+    /// A hole in the board for variants: no one can play it, and no line
+    /// through it can be won. See
+    /// [`GameEngineBuilder::blocked_cells`](crate::GameEngineBuilder::blocked_cells).
+    Blocked,
 }
 
-// The standard library's `fmt` module is imported for printing and formatting.
-use std::fmt;
-
 impl fmt::Display for Cell {
     /// Implements the `Display` trait to allow a `Cell` to be printed cleanly.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -35,21 +56,498 @@ impl fmt::Display for Cell {
             Cell::X => write!(f, "X"),
             Cell::O => write!(f, "O"),
             Cell::Empty => write!(f, "."),
+            Cell::Blocked => write!(f, "#"),
         }
     }
 }
 
+/// The kind of a cell by its position on the board.
+///
+/// See [`GameEngine::cell_kind`](crate::GameEngine::cell_kind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellKind {
+    /// The middle cell, index 4.
+    Center,
+    /// One of the four corners: indices 0, 2, 6 and 8.
+    Corner,
+    /// One of the four cells between two corners: indices 1, 3, 5 and 7.
+    Edge,
+}
+
+/// A coarse stage of the game, judged by the number of marks on the board.
+///
+/// See [`GameEngine::phase`](crate::GameEngine::phase).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePhase {
+    /// 0 to 2 marks: the players are choosing their first squares.
+    Opening,
+    /// 3 to 6 marks: threats and forks are made and blocked.
+    Midgame,
+    /// 7 to 9 marks: only a few cells are left to fill.
+    Endgame,
+}
+
+impl GamePhase {
+    /// Returns the phase of a game with `moves` marks on the board.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::GamePhase;
+    ///
+    /// assert_eq!(GamePhase::from_move_count(2), GamePhase::Opening);
+    /// assert_eq!(GamePhase::from_move_count(3), GamePhase::Midgame);
+    /// assert_eq!(GamePhase::from_move_count(7), GamePhase::Endgame);
+    /// ```
+    pub fn from_move_count(moves: usize) -> Self {
+        match moves {
+            0..=2 => GamePhase::Opening,
+            3..=6 => GamePhase::Midgame,
+            _ => GamePhase::Endgame,
+        }
+    }
+}
+
+/// One of the 8 symmetries of the board: the rotations and reflections
+/// that map the 3x3 grid onto itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Leaves the board unchanged.
+    Identity,
+    /// Rotates the board 90° clockwise.
+    Rotate90,
+    /// Rotates the board 180°.
+    Rotate180,
+    /// Rotates the board 270° clockwise (90° counter-clockwise).
+    Rotate270,
+    /// Mirrors the board left to right, swapping the left and right columns.
+    FlipHorizontal,
+    /// Mirrors the board top to bottom, swapping the top and bottom rows.
+    FlipVertical,
+    /// Reflects the board across the main diagonal (0, 4, 8).
+    FlipDiagonal,
+    /// Reflects the board across the anti-diagonal (2, 4, 6).
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+    /// All 8 symmetries, starting with [`Symmetry::Identity`].
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::FlipDiagonal,
+        Symmetry::FlipAntiDiagonal,
+    ];
+
+    /// Returns the index that cell `index` is moved to by this symmetry.
+    ///
+    /// # Panics
+    /// Panics if `index >= 9`.
+    pub fn map_index(self, index: usize) -> usize {
+        SYMMETRIES[self as usize][index]
+    }
+
+    /// Returns a transformed copy of `board`.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{Cell, Symmetry};
+    ///
+    /// let mut board = [Cell::Empty; 9];
+    /// board[0] = Cell::X;
+    ///
+    /// // Rotating clockwise moves the top-left corner to the top-right.
+    /// assert_eq!(Symmetry::Rotate90.apply(&board)[2], Cell::X);
+    /// ```
+    pub fn apply(self, board: &[Cell; 9]) -> [Cell; 9] {
+        let mut transformed = [Cell::Empty; 9];
+        for (i, &cell) in board.iter().enumerate() {
+            transformed[self.map_index(i)] = cell;
+        }
+        transformed
+    }
+}
+
 /// Represents the overall state of the game.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameState {
     Win(Player),
     Tie,
     InProgress,
+    /// The player resigned with [`GameEngine::resign`](crate::GameEngine::resign),
+    /// giving the game to their opponent.
+    Resigned(Player),
+    /// The players agreed to a draw with
+    /// [`GameEngine::agree_draw`](crate::GameEngine::agree_draw).
+    DrawAgreed,
+}
+
+impl GameState {
+    /// Returns the player who won the game, on the board or by their
+    /// opponent's resignation, or `None` if no one has.
+    ///
+    /// # Example
+    /// ```
+    /// use xo_core::{GameState, Player};
+    ///
+    /// assert_eq!(GameState::Win(Player::X).winner(), Some(Player::X));
+    /// assert_eq!(GameState::Resigned(Player::X).winner(), Some(Player::O));
+    /// assert_eq!(GameState::DrawAgreed.winner(), None);
+    /// ```
+    pub fn winner(&self) -> Option<Player> {
+        match *self {
+            GameState::Win(player) => Some(player),
+            GameState::Resigned(player) => Some(player.opponent()),
+            GameState::Tie | GameState::InProgress | GameState::DrawAgreed => None,
+        }
+    }
+}
+
+impl fmt::Display for GameState {
+    /// Implements the `Display` trait to print a human-readable game result.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameState::Win(player) => write!(f, "{} wins", player),
+            GameState::Tie => write!(f, "Draw"),
+            GameState::InProgress => write!(f, "In progress"),
+            GameState::Resigned(player) => {
+                write!(f, "{} resigns, {} wins", player, player.opponent())
+            }
+            GameState::DrawAgreed => write!(f, "Draw by agreement"),
+        }
+    }
+}
+
+/// The strategic reason behind a recommended move.
+///
+/// Categories are listed in order of priority.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HintCategory {
+    /// The move wins the game immediately.
+    Win,
+    /// The move blocks the opponent from winning on their next turn.
+    Block,
+    /// The move creates two winning threats at once.
+    Fork,
+    /// The move prevents the opponent from creating a fork.
+    BlockFork,
+    /// The move takes the center.
+    Center,
+    /// The move takes a corner.
+    Corner,
+    /// No special reason; the move is simply sound.
+    Neutral,
+}
+
+/// A recommended move together with the reason it is recommended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hint {
+    /// The 0-based cell index (0–8) of the recommended move.
+    pub index: usize,
+    /// Why the move is recommended.
+    pub category: HintCategory,
+}
+
+/// A summary of a finished game, returned by [`GameEngine::result`].
+///
+/// Requires the `alloc` feature.
+///
+/// [`GameEngine::result`]: crate::GameEngine::result
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    /// How the game ended; never [`GameState::InProgress`].
+    pub state: GameState,
+    /// The cell indices of the winning line, or `None` for a tie.
+    pub winning_line: Option<Vec<usize>>,
+    /// The moves played, in order, as cell indices.
+    pub moves: Vec<usize>,
+    /// The number of marks on the board.
+    pub move_count: usize,
+}
+
+/// How the AI scores the outcomes of the positions it searches, from the
+/// point of view of the player it is choosing a move for.
+///
+/// The default scores a win as `10`, a loss as `-10` and a tie as `0`, which
+/// gives perfect play. See [`GameEngine::scoring`](crate::GameEngine::scoring).
+///
+/// # Example
+/// ```
+/// use xo_core::{GameEngine, ScoringConfig};
+///
+/// let mut game = GameEngine::new();
+/// // Value a draw above a win.
+/// game.scoring = ScoringConfig { win: 1, loss: -10, tie: 5 };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringConfig {
+    /// The score of a win.
+    pub win: i32,
+    /// The score of a loss.
+    pub loss: i32,
+    /// The score of a tie.
+    pub tie: i32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            win: 10,
+            loss: -10,
+            tie: 0,
+        }
+    }
+}
+
+/// How the AI chooses among moves that are equally good.
+///
+/// The choice never changes the AI's strength, only its style. See
+/// [`GameEngine::tie_break`](crate::GameEngine::tie_break).
+///
+/// # Example
+/// ```
+/// use xo_core::{GameEngine, TieBreak};
+///
+/// let mut game = GameEngine::new();
+/// // Every opening draws, so the tie-break decides.
+/// assert_eq!(game.get_best_move(), Some(0));
+/// game.tie_break = TieBreak::PreferCenter;
+/// assert_eq!(game.get_best_move(), Some(4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TieBreak {
+    /// The move with the lowest index, the default.
+    #[default]
+    LowestIndex,
+    /// The move with the highest index.
+    HighestIndex,
+    /// The center if it is among the best moves, otherwise the lowest index.
+    PreferCenter,
+    /// The first corner among the best moves, otherwise the lowest index.
+    PreferCorners,
+    /// A pseudo-random move, derived from the engine's seed and the position
+    /// like [`GameEngine::randomize_ties`](crate::GameEngine::randomize_ties).
+    Random,
+}
+
+/// A read-only copy of a game at one moment, returned by
+/// [`GameEngine::snapshot`](crate::GameEngine::snapshot).
+///
+/// A snapshot doesn't change when the engine it was taken from does, and it
+/// is `Send + Sync`, so it can be handed to other threads, e.g. for rendering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameSnapshot {
+    pub(crate) board: [Cell; 9],
+    pub(crate) current_player: Player,
+    pub(crate) state: GameState,
+}
+
+impl GameSnapshot {
+    /// Returns the board at the time of the snapshot.
+    pub fn board(&self) -> &[Cell; 9] {
+        &self.board
+    }
+
+    /// Returns the player whose turn it was.
+    pub fn current_player(&self) -> Player {
+        self.current_player
+    }
+
+    /// Returns the state of the game.
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Returns `true` if the game was finished (either win or draw).
+    pub fn is_over(&self) -> bool {
+        self.state != GameState::InProgress
+    }
+}
+
+/// The permanent record of a finished game, returned by
+/// [`GameEngine::into_record`](crate::GameEngine::into_record).
+///
+/// Unlike the engine it came from, a record can't be played on, so it is
+/// suited to storing and rendering completed games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameRecord {
+    pub(crate) board: [Cell; 9],
+    pub(crate) state: GameState,
+    /// The moves played, of which the first `move_len` are used.
+    pub(crate) moves: [usize; 9],
+    pub(crate) move_len: usize,
+    /// The cells of the winning line, of which the first `line_len` are used.
+    pub(crate) line: [usize; 3],
+    pub(crate) line_len: usize,
+}
+
+impl GameRecord {
+    /// Returns the final board.
+    pub fn board(&self) -> &[Cell; 9] {
+        &self.board
+    }
+
+    /// Returns how the game ended; never [`GameState::InProgress`].
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Returns the winner, on the board or by resignation, or `None` for a draw.
+    pub fn winner(&self) -> Option<Player> {
+        self.state.winner()
+    }
+
+    /// Returns the cell indices of the winning line, or `None` if the game
+    /// wasn't won on the board.
+    pub fn winning_line(&self) -> Option<&[usize]> {
+        (self.line_len > 0).then(|| &self.line[..self.line_len])
+    }
+
+    /// Returns the moves played, in order, as cell indices.
+    pub fn moves(&self) -> &[usize] {
+        &self.moves[..self.move_len]
+    }
+}
+
+/// A receipt for a move made with
+/// [`GameEngine::make_move_tok`](crate::GameEngine::make_move_tok), holding
+/// what [`GameEngine::undo_tok`](crate::GameEngine::undo_tok) needs to take
+/// exactly that move back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[must_use = "a move token is needed to undo its move"]
+pub struct MoveToken {
+    pub(crate) index: usize,
+    /// The state of the game before the move.
+    pub(crate) state: GameState,
+    /// The history entry the move overwrote, which may be an undone move.
+    pub(crate) replaced: usize,
+    /// The number of recorded moves before the move discarded undone ones.
+    pub(crate) recorded_len: usize,
+}
+
+impl MoveToken {
+    /// Returns the cell index the move was played at.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Errors that can occur when building a game from an existing board.
+#[derive(Debug, PartialEq)]
+pub enum BoardError {
+    /// The board doesn't have exactly 9 cells; holds the actual length.
+    InvalidLength(usize),
+    /// X must have the same number of marks as O, or exactly one more,
+    /// since X moves first and the players alternate.
+    InvalidMarkCounts { x: usize, o: usize },
+    /// Both players have completed a line.
+    MultipleWinners,
+    /// A player has completed a line, but the mark counts show that the
+    /// other player moved afterwards.
+    MoveAfterWin,
+    /// An encoded board has bits set above its 18 cell bits; holds the encoding.
+    InvalidEncoding(u32),
+    /// The mark counts show it isn't the declared player's turn; holds the
+    /// declared player.
+    WrongTurn(Player),
+}
+
+impl fmt::Display for BoardError {
+    /// Implements the `Display` trait to describe why a board was rejected.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::InvalidLength(len) => {
+                write!(f, "board must have 9 cells, found {}", len)
+            }
+            BoardError::InvalidMarkCounts { x, o } => write!(
+                f,
+                "impossible mark counts: {} X and {} O (X must have as many as O or one more)",
+                x, o
+            ),
+            BoardError::MultipleWinners => write!(f, "both players have completed a line"),
+            BoardError::MoveAfterWin => write!(f, "a move was played after the game was won"),
+            BoardError::InvalidEncoding(bits) => {
+                write!(f, "{:#x} is not an encoded board", bits)
+            }
+            BoardError::WrongTurn(player) => {
+                write!(f, "the mark counts show it isn't {}'s turn", player)
+            }
+        }
+    }
+}
+
+/// Errors that can occur when reading a game from move notation.
+///
+/// Each variant holds the position of the offending move in the transcript.
+#[derive(Debug, PartialEq)]
+pub enum NotationError {
+    /// The move isn't a mark (`X` or `O`) followed by a column `a`–`c` and a row `1`–`3`.
+    InvalidMove(usize),
+    /// The move is by the player who isn't on turn.
+    WrongPlayer(usize),
+    /// The move can't be made in the position reached.
+    IllegalMove(usize, MoveError),
+}
+
+impl fmt::Display for NotationError {
+    /// Implements the `Display` trait to describe why a transcript was rejected.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotationError::InvalidMove(position) => {
+                write!(f, "move {} is not valid notation", position + 1)
+            }
+            NotationError::WrongPlayer(position) => {
+                write!(f, "move {} is played out of turn", position + 1)
+            }
+            NotationError::IllegalMove(position, error) => {
+                write!(f, "move {} is illegal ({:?})", position + 1, error)
+            }
+        }
+    }
+}
+
+/// Errors that can occur when parsing a typed move with
+/// [`GameEngine::parse_move`](crate::GameEngine::parse_move).
+#[derive(Debug, PartialEq)]
+pub enum ParseMoveError {
+    /// The input isn't an index, a `row,col` pair or chess-like coordinates.
+    InvalidFormat,
+    /// The input names a cell that isn't on the board.
+    OutOfBounds,
+}
+
+impl fmt::Display for ParseMoveError {
+    /// Implements the `Display` trait to describe why a move couldn't be parsed.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMoveError::InvalidFormat => {
+                write!(f, "expected an index 0-8, a row,col pair or a1-c3")
+            }
+            ParseMoveError::OutOfBounds => write!(f, "that cell is not on the board"),
+        }
+    }
 }
 
-/// Errors that can occur when attempting to make a move.
+/// Errors that can occur when attempting to make, undo, or redo a move.
 #[derive(Debug, PartialEq)]
 pub enum MoveError {
     OutOfBounds,
     CellOccupied,
+    /// The cell is [blocked](Cell::Blocked), so no one can play it.
+    CellBlocked,
+    /// No move has been played that could be undone.
+    NothingToUndo,
+    /// No undone move is available to redo.
+    NothingToRedo,
+    /// The move was attributed to the player whose turn it isn't.
+    OutOfTurn,
+    /// The game is over (won, tied, resigned or drawn by agreement), so no
+    /// more moves can be made. See [`GameEngine::is_over`].
+    ///
+    /// [`GameEngine::is_over`]: crate::GameEngine::is_over
+    GameOver,
 }
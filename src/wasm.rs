@@ -0,0 +1,97 @@
+//! JavaScript bindings for the engine, built with `wasm-bindgen`.
+
+use crate::game_engine::GameEngine;
+use alloc::string::{String, ToString};
+use wasm_bindgen::prelude::*;
+
+/// A [`GameEngine`] wrapped for JavaScript, with AI **enabled**.
+///
+/// Methods return plain numbers, booleans and strings instead of Rust types:
+/// errors become `false` and missing moves `-1`.
+///
+/// Requires the `wasm` feature.
+///
+/// # Example
+/// Build the crate as a WebAssembly library and generate the bindings:
+///
+/// ```sh
+/// cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+/// wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/xo_core.wasm
+/// ```
+///
+/// then drive a game from JavaScript:
+///
+/// ```js
+/// import init, { Game } from "./pkg/xo_core.js";
+///
+/// await init();
+/// const game = new Game();
+/// game.make_move(4);
+/// const reply = game.get_best_move();
+/// if (reply !== -1) {
+///   game.make_move(reply);
+/// }
+/// console.log(game.board());
+/// console.log(game.state()); // "In progress"
+/// ```
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct Game(GameEngine);
+
+#[wasm_bindgen]
+impl Game {
+    /// Creates a new game where X moves first.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Game {
+        Game(GameEngine::new())
+    }
+
+    /// Plays the current player's mark at `index`, returning `false` if the
+    /// move is illegal or the game is over.
+    pub fn make_move(&mut self, index: usize) -> bool {
+        self.0.make_move(index).is_ok()
+    }
+
+    /// Returns the AI's move for the current player, or `-1` if there is none.
+    pub fn get_best_move(&self) -> i32 {
+        self.0.get_best_move().map_or(-1, |index| index as i32)
+    }
+
+    /// Returns the board drawn as text, as [`GameEngine`]'s `Display` does.
+    pub fn board(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Returns the state of the game as text, such as `"X wins"` or `"Draw"`.
+    pub fn state(&self) -> String {
+        self.0.check_state().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapper_reports_moves_as_plain_values() {
+        let mut game = Game::new();
+        assert!(game.make_move(4));
+        assert!(!game.make_move(4));
+        assert!(!game.make_move(9));
+        assert_eq!(game.state(), "In progress");
+
+        // A corner is the only move that holds the draw after the center.
+        assert!([0, 2, 6, 8].contains(&game.get_best_move()));
+        let mut engine = GameEngine::new();
+        engine.make_move(4).unwrap();
+        assert_eq!(game.board(), engine.to_string());
+
+        // X takes the top row; a finished game has no best move.
+        let mut game = Game::new();
+        for index in [0, 3, 1, 4, 2] {
+            assert!(game.make_move(index));
+        }
+        assert_eq!(game.state(), "X wins");
+        assert_eq!(game.get_best_move(), -1);
+    }
+}